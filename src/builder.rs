@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use crate::{BJJMatch, Clock, Competitor, CompetitorNumber, DurationTable, MatchConfigError, MatchInformation, RuleSet};
+
+/// Mats available when the builder isn't told otherwise.
+pub const DEFAULT_MAT_COUNT: usize = 20;
+
+/// Builds a `BJJMatch`, checking the configuration makes sense before creating it. Unless a
+/// duration is given, the match length comes from the competitors' divisions, the shorter of the
+/// two when a master faces an adult.
+pub struct MatchBuilder {
+    info: MatchInformation,
+    match_time_minutes: Option<usize>,
+    durations: DurationTable,
+    mat_count: usize,
+    mercy_margin: Option<usize>,
+    rules: Box<dyn RuleSet>,
+    clock: Option<Arc<dyn Clock>>
+}
+
+impl Default for MatchBuilder {
+    fn default() -> Self {
+        Self {
+            info: MatchInformation::default(),
+            match_time_minutes: None,
+            durations: DurationTable::default(),
+            mat_count: DEFAULT_MAT_COUNT,
+            mercy_margin: None,
+            rules: Box::default(),
+            clock: None
+        }
+    }
+}
+
+impl MatchBuilder {
+    pub fn competitor_one(mut self, competitor: Competitor) -> MatchBuilder {
+        self.info.competitor_one = competitor;
+        self
+    }
+
+    pub fn competitor_two(mut self, competitor: Competitor) -> MatchBuilder {
+        self.info.competitor_two = competitor;
+        self
+    }
+
+    pub fn match_time_minutes(mut self, minutes: usize) -> MatchBuilder {
+        self.match_time_minutes = Some(minutes);
+        self
+    }
+
+    pub fn mat_number(mut self, mat_number: usize) -> MatchBuilder {
+        self.info.mat_number = mat_number;
+        self
+    }
+
+    pub fn fight_number(mut self, fight_number: usize) -> MatchBuilder {
+        self.info.fight_number = fight_number;
+        self
+    }
+
+    /// Uses all of the given match information, e.g. as filled in by a settings form.
+    pub fn info(mut self, info: MatchInformation) -> MatchBuilder {
+        self.match_time_minutes = Some(info.match_time_minutes);
+        self.info = info;
+        self
+    }
+
+    /// Looks up match lengths in the given table instead of the IBJJF one.
+    pub fn durations(mut self, durations: DurationTable) -> MatchBuilder {
+        self.durations = durations;
+        self
+    }
+
+    /// The number of mats at the event, so mats 1 to `mat_count` are valid.
+    pub fn mat_count(mut self, mat_count: usize) -> MatchBuilder {
+        self.mat_count = mat_count;
+        self
+    }
+
+    /// Ends the match as soon as either competitor leads by `margin` points or more.
+    pub fn mercy_rule(mut self, margin: usize) -> MatchBuilder {
+        self.mercy_margin = Some(margin);
+        self
+    }
+
+    pub fn rules(mut self, rules: Box<dyn RuleSet>) -> MatchBuilder {
+        self.rules = rules;
+        self
+    }
+
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> MatchBuilder {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// The match length: the one given, or the one for the competitors' divisions.
+    pub fn resolved_match_time_minutes(&self) -> usize {
+        self.match_time_minutes
+            .unwrap_or_else(|| self.durations.minutes_for(&self.info.competitor_one, &self.info.competitor_two))
+    }
+
+    /// Checks the configuration without building the match.
+    pub fn validate(&self) -> Result<(), MatchConfigError> {
+        let info = &self.info;
+        let minutes = self.resolved_match_time_minutes();
+        for (number, competitor) in [(CompetitorNumber::One, &info.competitor_one), (CompetitorNumber::Two, &info.competitor_two)] {
+            if competitor.first_name.trim().is_empty() || competitor.last_name.trim().is_empty() {
+                return Err(MatchConfigError::MissingName(number));
+            }
+        }
+
+        if same_athlete(&info.competitor_one, &info.competitor_two) {
+            return Err(MatchConfigError::SameCompetitor);
+        }
+
+        let (age_one, age_two) = (info.competitor_one.age_division, info.competitor_two.age_division);
+        if !age_one.can_face(age_two) {
+            return Err(MatchConfigError::MixedAgeDivisions(age_one, age_two));
+        }
+
+        for competitor in [&info.competitor_one, &info.competitor_two] {
+            let range = competitor.age_division.match_minutes_range();
+            if !range.contains(&minutes) {
+                return Err(MatchConfigError::DurationOutOfRange {
+                    minutes,
+                    division: competitor.age_division,
+                    min: *range.start(),
+                    max: *range.end()
+                });
+            }
+        }
+
+        if info.mat_number == 0 || info.mat_number > self.mat_count {
+            return Err(MatchConfigError::InvalidMat { mat_number: info.mat_number, mat_count: self.mat_count });
+        }
+
+        if info.fight_number == 0 {
+            return Err(MatchConfigError::InvalidFightNumber);
+        }
+
+        if self.mercy_margin == Some(0) {
+            return Err(MatchConfigError::InvalidMercyMargin);
+        }
+
+        Ok(())
+    }
+
+    pub fn build(self) -> Result<BJJMatch, MatchConfigError> {
+        self.validate()?;
+
+        let minutes = self.resolved_match_time_minutes();
+        let info = self.info;
+        let mut bjj_match = BJJMatch::new(info.competitor_one, info.competitor_two, minutes, info.mat_number, info.fight_number)
+            .with_rules(self.rules);
+        bjj_match.set_mercy_rule(self.mercy_margin);
+        Ok(match self.clock {
+            Some(clock) => bjj_match.with_clock(clock),
+            None => bjj_match
+        })
+    }
+}
+
+/// Whether the two competitors are the same person: the same athlete ID if both have one,
+/// otherwise the same name and team.
+fn same_athlete(one: &Competitor, two: &Competitor) -> bool {
+    if let (Some(one_id), Some(two_id)) = (&one.athlete_id, &two.athlete_id) {
+        return one_id == two_id;
+    }
+
+    let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+    same(&one.first_name, &two.first_name) && same(&one.last_name, &two.last_name) && same(&one.team_name, &two.team_name)
+}
@@ -0,0 +1,352 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+macro_rules! countries {
+    ($($country:ident => $alpha2:literal, $alpha3:literal, $name:literal;)*) => {
+        /// A country from ISO 3166-1.
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+        pub enum Country {
+            $($country),*
+        }
+
+        impl Country {
+            /// Every country, in alphabetical order of name.
+            pub const ALL: &'static [Country] = &[$(Country::$country),*];
+
+            /// The two-letter ISO 3166-1 alpha-2 code, e.g. "AU".
+            pub fn alpha2(self) -> &'static str {
+                match self {
+                    $(Country::$country => $alpha2),*
+                }
+            }
+
+            /// The three-letter ISO 3166-1 alpha-3 code, e.g. "AUS".
+            pub fn alpha3(self) -> &'static str {
+                match self {
+                    $(Country::$country => $alpha3),*
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Country::$country => $name),*
+                }
+            }
+        }
+    };
+}
+
+countries! {
+    Afghanistan => "AF", "AFG", "Afghanistan";
+    AlandIslands => "AX", "ALA", "Åland Islands";
+    Albania => "AL", "ALB", "Albania";
+    Algeria => "DZ", "DZA", "Algeria";
+    AmericanSamoa => "AS", "ASM", "American Samoa";
+    Andorra => "AD", "AND", "Andorra";
+    Angola => "AO", "AGO", "Angola";
+    Anguilla => "AI", "AIA", "Anguilla";
+    Antarctica => "AQ", "ATA", "Antarctica";
+    AntiguaAndBarbuda => "AG", "ATG", "Antigua and Barbuda";
+    Argentina => "AR", "ARG", "Argentina";
+    Armenia => "AM", "ARM", "Armenia";
+    Aruba => "AW", "ABW", "Aruba";
+    Australia => "AU", "AUS", "Australia";
+    Austria => "AT", "AUT", "Austria";
+    Azerbaijan => "AZ", "AZE", "Azerbaijan";
+    Bahamas => "BS", "BHS", "Bahamas";
+    Bahrain => "BH", "BHR", "Bahrain";
+    Bangladesh => "BD", "BGD", "Bangladesh";
+    Barbados => "BB", "BRB", "Barbados";
+    Belarus => "BY", "BLR", "Belarus";
+    Belgium => "BE", "BEL", "Belgium";
+    Belize => "BZ", "BLZ", "Belize";
+    Benin => "BJ", "BEN", "Benin";
+    Bermuda => "BM", "BMU", "Bermuda";
+    Bhutan => "BT", "BTN", "Bhutan";
+    Bolivia => "BO", "BOL", "Bolivia";
+    CaribbeanNetherlands => "BQ", "BES", "Bonaire, Sint Eustatius and Saba";
+    BosniaAndHerzegovina => "BA", "BIH", "Bosnia and Herzegovina";
+    Botswana => "BW", "BWA", "Botswana";
+    BouvetIsland => "BV", "BVT", "Bouvet Island";
+    Brazil => "BR", "BRA", "Brazil";
+    BritishIndianOceanTerritory => "IO", "IOT", "British Indian Ocean Territory";
+    BritishVirginIslands => "VG", "VGB", "British Virgin Islands";
+    Brunei => "BN", "BRN", "Brunei";
+    Bulgaria => "BG", "BGR", "Bulgaria";
+    BurkinaFaso => "BF", "BFA", "Burkina Faso";
+    Burundi => "BI", "BDI", "Burundi";
+    CaboVerde => "CV", "CPV", "Cabo Verde";
+    Cambodia => "KH", "KHM", "Cambodia";
+    Cameroon => "CM", "CMR", "Cameroon";
+    Canada => "CA", "CAN", "Canada";
+    CaymanIslands => "KY", "CYM", "Cayman Islands";
+    CentralAfricanRepublic => "CF", "CAF", "Central African Republic";
+    Chad => "TD", "TCD", "Chad";
+    Chile => "CL", "CHL", "Chile";
+    China => "CN", "CHN", "China";
+    ChristmasIsland => "CX", "CXR", "Christmas Island";
+    CocosIslands => "CC", "CCK", "Cocos (Keeling) Islands";
+    Colombia => "CO", "COL", "Colombia";
+    Comoros => "KM", "COM", "Comoros";
+    Congo => "CG", "COG", "Congo";
+    CookIslands => "CK", "COK", "Cook Islands";
+    CostaRica => "CR", "CRI", "Costa Rica";
+    CoteDIvoire => "CI", "CIV", "Côte d'Ivoire";
+    Croatia => "HR", "HRV", "Croatia";
+    Cuba => "CU", "CUB", "Cuba";
+    Curacao => "CW", "CUW", "Curaçao";
+    Cyprus => "CY", "CYP", "Cyprus";
+    Czechia => "CZ", "CZE", "Czechia";
+    DemocraticRepublicOfTheCongo => "CD", "COD", "Democratic Republic of the Congo";
+    Denmark => "DK", "DNK", "Denmark";
+    Djibouti => "DJ", "DJI", "Djibouti";
+    Dominica => "DM", "DMA", "Dominica";
+    DominicanRepublic => "DO", "DOM", "Dominican Republic";
+    Ecuador => "EC", "ECU", "Ecuador";
+    Egypt => "EG", "EGY", "Egypt";
+    ElSalvador => "SV", "SLV", "El Salvador";
+    EquatorialGuinea => "GQ", "GNQ", "Equatorial Guinea";
+    Eritrea => "ER", "ERI", "Eritrea";
+    Estonia => "EE", "EST", "Estonia";
+    Eswatini => "SZ", "SWZ", "Eswatini";
+    Ethiopia => "ET", "ETH", "Ethiopia";
+    FalklandIslands => "FK", "FLK", "Falkland Islands";
+    FaroeIslands => "FO", "FRO", "Faroe Islands";
+    Fiji => "FJ", "FJI", "Fiji";
+    Finland => "FI", "FIN", "Finland";
+    France => "FR", "FRA", "France";
+    FrenchGuiana => "GF", "GUF", "French Guiana";
+    FrenchPolynesia => "PF", "PYF", "French Polynesia";
+    FrenchSouthernTerritories => "TF", "ATF", "French Southern Territories";
+    Gabon => "GA", "GAB", "Gabon";
+    Gambia => "GM", "GMB", "Gambia";
+    Georgia => "GE", "GEO", "Georgia";
+    Germany => "DE", "DEU", "Germany";
+    Ghana => "GH", "GHA", "Ghana";
+    Gibraltar => "GI", "GIB", "Gibraltar";
+    Greece => "GR", "GRC", "Greece";
+    Greenland => "GL", "GRL", "Greenland";
+    Grenada => "GD", "GRD", "Grenada";
+    Guadeloupe => "GP", "GLP", "Guadeloupe";
+    Guam => "GU", "GUM", "Guam";
+    Guatemala => "GT", "GTM", "Guatemala";
+    Guernsey => "GG", "GGY", "Guernsey";
+    Guinea => "GN", "GIN", "Guinea";
+    GuineaBissau => "GW", "GNB", "Guinea-Bissau";
+    Guyana => "GY", "GUY", "Guyana";
+    Haiti => "HT", "HTI", "Haiti";
+    HeardAndMcDonaldIslands => "HM", "HMD", "Heard Island and McDonald Islands";
+    HolySee => "VA", "VAT", "Holy See";
+    Honduras => "HN", "HND", "Honduras";
+    HongKong => "HK", "HKG", "Hong Kong";
+    Hungary => "HU", "HUN", "Hungary";
+    Iceland => "IS", "ISL", "Iceland";
+    India => "IN", "IND", "India";
+    Indonesia => "ID", "IDN", "Indonesia";
+    Iran => "IR", "IRN", "Iran";
+    Iraq => "IQ", "IRQ", "Iraq";
+    Ireland => "IE", "IRL", "Ireland";
+    IsleOfMan => "IM", "IMN", "Isle of Man";
+    Israel => "IL", "ISR", "Israel";
+    Italy => "IT", "ITA", "Italy";
+    Jamaica => "JM", "JAM", "Jamaica";
+    Japan => "JP", "JPN", "Japan";
+    Jersey => "JE", "JEY", "Jersey";
+    Jordan => "JO", "JOR", "Jordan";
+    Kazakhstan => "KZ", "KAZ", "Kazakhstan";
+    Kenya => "KE", "KEN", "Kenya";
+    Kiribati => "KI", "KIR", "Kiribati";
+    Kuwait => "KW", "KWT", "Kuwait";
+    Kyrgyzstan => "KG", "KGZ", "Kyrgyzstan";
+    Laos => "LA", "LAO", "Laos";
+    Latvia => "LV", "LVA", "Latvia";
+    Lebanon => "LB", "LBN", "Lebanon";
+    Lesotho => "LS", "LSO", "Lesotho";
+    Liberia => "LR", "LBR", "Liberia";
+    Libya => "LY", "LBY", "Libya";
+    Liechtenstein => "LI", "LIE", "Liechtenstein";
+    Lithuania => "LT", "LTU", "Lithuania";
+    Luxembourg => "LU", "LUX", "Luxembourg";
+    Macao => "MO", "MAC", "Macao";
+    Madagascar => "MG", "MDG", "Madagascar";
+    Malawi => "MW", "MWI", "Malawi";
+    Malaysia => "MY", "MYS", "Malaysia";
+    Maldives => "MV", "MDV", "Maldives";
+    Mali => "ML", "MLI", "Mali";
+    Malta => "MT", "MLT", "Malta";
+    MarshallIslands => "MH", "MHL", "Marshall Islands";
+    Martinique => "MQ", "MTQ", "Martinique";
+    Mauritania => "MR", "MRT", "Mauritania";
+    Mauritius => "MU", "MUS", "Mauritius";
+    Mayotte => "YT", "MYT", "Mayotte";
+    Mexico => "MX", "MEX", "Mexico";
+    Micronesia => "FM", "FSM", "Micronesia";
+    Moldova => "MD", "MDA", "Moldova";
+    Monaco => "MC", "MCO", "Monaco";
+    Mongolia => "MN", "MNG", "Mongolia";
+    Montenegro => "ME", "MNE", "Montenegro";
+    Montserrat => "MS", "MSR", "Montserrat";
+    Morocco => "MA", "MAR", "Morocco";
+    Mozambique => "MZ", "MOZ", "Mozambique";
+    Myanmar => "MM", "MMR", "Myanmar";
+    Namibia => "NA", "NAM", "Namibia";
+    Nauru => "NR", "NRU", "Nauru";
+    Nepal => "NP", "NPL", "Nepal";
+    Netherlands => "NL", "NLD", "Netherlands";
+    NewCaledonia => "NC", "NCL", "New Caledonia";
+    NewZealand => "NZ", "NZL", "New Zealand";
+    Nicaragua => "NI", "NIC", "Nicaragua";
+    Niger => "NE", "NER", "Niger";
+    Nigeria => "NG", "NGA", "Nigeria";
+    Niue => "NU", "NIU", "Niue";
+    NorfolkIsland => "NF", "NFK", "Norfolk Island";
+    NorthKorea => "KP", "PRK", "North Korea";
+    NorthMacedonia => "MK", "MKD", "North Macedonia";
+    NorthernMarianaIslands => "MP", "MNP", "Northern Mariana Islands";
+    Norway => "NO", "NOR", "Norway";
+    Oman => "OM", "OMN", "Oman";
+    Pakistan => "PK", "PAK", "Pakistan";
+    Palau => "PW", "PLW", "Palau";
+    Palestine => "PS", "PSE", "Palestine";
+    Panama => "PA", "PAN", "Panama";
+    PapuaNewGuinea => "PG", "PNG", "Papua New Guinea";
+    Paraguay => "PY", "PRY", "Paraguay";
+    Peru => "PE", "PER", "Peru";
+    Philippines => "PH", "PHL", "Philippines";
+    Pitcairn => "PN", "PCN", "Pitcairn";
+    Poland => "PL", "POL", "Poland";
+    Portugal => "PT", "PRT", "Portugal";
+    PuertoRico => "PR", "PRI", "Puerto Rico";
+    Qatar => "QA", "QAT", "Qatar";
+    Reunion => "RE", "REU", "Réunion";
+    Romania => "RO", "ROU", "Romania";
+    Russia => "RU", "RUS", "Russia";
+    Rwanda => "RW", "RWA", "Rwanda";
+    SaintBarthelemy => "BL", "BLM", "Saint Barthélemy";
+    SaintHelena => "SH", "SHN", "Saint Helena, Ascension and Tristan da Cunha";
+    SaintKittsAndNevis => "KN", "KNA", "Saint Kitts and Nevis";
+    SaintLucia => "LC", "LCA", "Saint Lucia";
+    SaintMartin => "MF", "MAF", "Saint Martin (French part)";
+    SaintPierreAndMiquelon => "PM", "SPM", "Saint Pierre and Miquelon";
+    SaintVincentAndTheGrenadines => "VC", "VCT", "Saint Vincent and the Grenadines";
+    Samoa => "WS", "WSM", "Samoa";
+    SanMarino => "SM", "SMR", "San Marino";
+    SaoTomeAndPrincipe => "ST", "STP", "Sao Tome and Principe";
+    SaudiArabia => "SA", "SAU", "Saudi Arabia";
+    Senegal => "SN", "SEN", "Senegal";
+    Serbia => "RS", "SRB", "Serbia";
+    Seychelles => "SC", "SYC", "Seychelles";
+    SierraLeone => "SL", "SLE", "Sierra Leone";
+    Singapore => "SG", "SGP", "Singapore";
+    SintMaarten => "SX", "SXM", "Sint Maarten (Dutch part)";
+    Slovakia => "SK", "SVK", "Slovakia";
+    Slovenia => "SI", "SVN", "Slovenia";
+    SolomonIslands => "SB", "SLB", "Solomon Islands";
+    Somalia => "SO", "SOM", "Somalia";
+    SouthAfrica => "ZA", "ZAF", "South Africa";
+    SouthGeorgia => "GS", "SGS", "South Georgia and the South Sandwich Islands";
+    SouthKorea => "KR", "KOR", "South Korea";
+    SouthSudan => "SS", "SSD", "South Sudan";
+    Spain => "ES", "ESP", "Spain";
+    SriLanka => "LK", "LKA", "Sri Lanka";
+    Sudan => "SD", "SDN", "Sudan";
+    Suriname => "SR", "SUR", "Suriname";
+    SvalbardAndJanMayen => "SJ", "SJM", "Svalbard and Jan Mayen";
+    Sweden => "SE", "SWE", "Sweden";
+    Switzerland => "CH", "CHE", "Switzerland";
+    Syria => "SY", "SYR", "Syria";
+    Taiwan => "TW", "TWN", "Taiwan";
+    Tajikistan => "TJ", "TJK", "Tajikistan";
+    Tanzania => "TZ", "TZA", "Tanzania";
+    Thailand => "TH", "THA", "Thailand";
+    TimorLeste => "TL", "TLS", "Timor-Leste";
+    Togo => "TG", "TGO", "Togo";
+    Tokelau => "TK", "TKL", "Tokelau";
+    Tonga => "TO", "TON", "Tonga";
+    TrinidadAndTobago => "TT", "TTO", "Trinidad and Tobago";
+    Tunisia => "TN", "TUN", "Tunisia";
+    Turkiye => "TR", "TUR", "Türkiye";
+    Turkmenistan => "TM", "TKM", "Turkmenistan";
+    TurksAndCaicosIslands => "TC", "TCA", "Turks and Caicos Islands";
+    Tuvalu => "TV", "TUV", "Tuvalu";
+    UsVirginIslands => "VI", "VIR", "U.S. Virgin Islands";
+    Uganda => "UG", "UGA", "Uganda";
+    Ukraine => "UA", "UKR", "Ukraine";
+    UnitedArabEmirates => "AE", "ARE", "United Arab Emirates";
+    UnitedKingdom => "GB", "GBR", "United Kingdom";
+    UnitedStates => "US", "USA", "United States";
+    UnitedStatesMinorOutlyingIslands => "UM", "UMI", "United States Minor Outlying Islands";
+    Uruguay => "UY", "URY", "Uruguay";
+    Uzbekistan => "UZ", "UZB", "Uzbekistan";
+    Vanuatu => "VU", "VUT", "Vanuatu";
+    Venezuela => "VE", "VEN", "Venezuela";
+    Vietnam => "VN", "VNM", "Vietnam";
+    WallisAndFutuna => "WF", "WLF", "Wallis and Futuna";
+    WesternSahara => "EH", "ESH", "Western Sahara";
+    Yemen => "YE", "YEM", "Yemen";
+    Zambia => "ZM", "ZMB", "Zambia";
+    Zimbabwe => "ZW", "ZWE", "Zimbabwe";
+}
+
+impl Country {
+    /// Looks up a country by its alpha-2 or alpha-3 code, ignoring case.
+    pub fn from_code(code: &str) -> Option<Country> {
+        let code = code.trim();
+        Country::ALL
+            .iter()
+            .copied()
+            .find(|country| country.alpha2().eq_ignore_ascii_case(code) || country.alpha3().eq_ignore_ascii_case(code))
+    }
+
+    /// Whether the country's name contains `query` or one of its codes is `query`, ignoring case.
+    pub fn matches_search(self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.name().to_lowercase().contains(&query)
+            || self.alpha2().eq_ignore_ascii_case(&query)
+            || self.alpha3().eq_ignore_ascii_case(&query)
+    }
+}
+
+impl fmt::Display for Country {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UnknownCountryCode(pub String);
+
+impl fmt::Display for UnknownCountryCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown country code: {}", self.0)
+    }
+}
+
+impl Error for UnknownCountryCode {}
+
+impl FromStr for Country {
+    type Err = UnknownCountryCode;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Country::from_code(code).ok_or_else(|| UnknownCountryCode(code.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Country, UnknownCountryCode};
+
+    #[test]
+    fn test_country_codes() {
+        assert_eq!(Country::from_code("br"), Some(Country::Brazil));
+        assert_eq!("USA".parse(), Ok(Country::UnitedStates));
+        assert_eq!("XX".parse::<Country>(), Err(UnknownCountryCode("XX".to_owned())));
+        assert_eq!(Country::Australia.alpha3(), "AUS");
+        assert_eq!(Country::ALL.len(), 249);
+        assert!(Country::NewZealand.matches_search("zeal"));
+        assert!(Country::NewZealand.matches_search("nz"));
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Belt {
+    White,
+    Grey,
+    Yellow,
+    Orange,
+    Green,
+    Blue,
+    Purple,
+    Brown,
+    Black
+}
+
+impl Belt {
+    /// Every belt, from lowest to highest rank.
+    pub const ALL: &'static [Belt] = &[
+        Belt::White, Belt::Grey, Belt::Yellow, Belt::Orange, Belt::Green,
+        Belt::Blue, Belt::Purple, Belt::Brown, Belt::Black
+    ];
+
+    /// The belt's colour as RGB.
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Belt::White => [0xf2, 0xf2, 0xf2],
+            Belt::Grey => [0x80, 0x80, 0x80],
+            Belt::Yellow => [0xf5, 0xd0, 0x1e],
+            Belt::Orange => [0xf0, 0x7d, 0x1a],
+            Belt::Green => [0x2e, 0x8b, 0x3a],
+            Belt::Blue => [0x1d, 0x4f, 0xb8],
+            Belt::Purple => [0x6a, 0x2c, 0x91],
+            Belt::Brown => [0x6b, 0x3e, 0x1f],
+            Belt::Black => [0x10, 0x10, 0x10]
+        }
+    }
+}
+
+impl fmt::Display for Belt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Belt::White => write!(f, "White"),
+            Belt::Grey => write!(f, "Grey"),
+            Belt::Yellow => write!(f, "Yellow"),
+            Belt::Orange => write!(f, "Orange"),
+            Belt::Green => write!(f, "Green"),
+            Belt::Blue => write!(f, "Blue"),
+            Belt::Purple => write!(f, "Purple"),
+            Belt::Brown => write!(f, "Brown"),
+            Belt::Black => write!(f, "Black"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum WeightClass {
+    Rooster,
+    LightFeather,
+    Feather,
+    Light,
+    Middle,
+    MediumHeavy,
+    Heavy,
+    SuperHeavy,
+    UltraHeavy,
+    Open
+}
+
+impl WeightClass {
+    /// Every weight class, from lightest to heaviest, then the open class.
+    pub const ALL: &'static [WeightClass] = &[
+        WeightClass::Rooster, WeightClass::LightFeather, WeightClass::Feather, WeightClass::Light,
+        WeightClass::Middle, WeightClass::MediumHeavy, WeightClass::Heavy, WeightClass::SuperHeavy,
+        WeightClass::UltraHeavy, WeightClass::Open
+    ];
+}
+
+impl fmt::Display for WeightClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightClass::Rooster => write!(f, "Rooster"),
+            WeightClass::LightFeather => write!(f, "Light Feather"),
+            WeightClass::Feather => write!(f, "Feather"),
+            WeightClass::Light => write!(f, "Light"),
+            WeightClass::Middle => write!(f, "Middle"),
+            WeightClass::MediumHeavy => write!(f, "Medium Heavy"),
+            WeightClass::Heavy => write!(f, "Heavy"),
+            WeightClass::SuperHeavy => write!(f, "Super Heavy"),
+            WeightClass::UltraHeavy => write!(f, "Ultra Heavy"),
+            WeightClass::Open => write!(f, "Open Class"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum AgeDivision {
+    Kids,
+    Juvenile,
+    Adult,
+    Master1,
+    Master2,
+    Master3,
+    Master4,
+    Master5,
+    Master6,
+    Master7
+}
+
+impl AgeDivision {
+    /// Every age division, from youngest to oldest.
+    pub const ALL: &'static [AgeDivision] = &[
+        AgeDivision::Kids, AgeDivision::Juvenile, AgeDivision::Adult, AgeDivision::Master1,
+        AgeDivision::Master2, AgeDivision::Master3, AgeDivision::Master4, AgeDivision::Master5,
+        AgeDivision::Master6, AgeDivision::Master7
+    ];
+
+    /// The match durations, in minutes, that make sense for the division. Adults allow long
+    /// submission-only and ADCC final matches.
+    pub fn match_minutes_range(self) -> RangeInclusive<usize> {
+        match self {
+            AgeDivision::Kids => 2..=6,
+            AgeDivision::Juvenile => 3..=8,
+            AgeDivision::Adult => 3..=30,
+            _ => 3..=10
+        }
+    }
+
+    /// Whether competitors from the two divisions can face each other. Adults and masters can,
+    /// since masters may enter the adult and younger masters divisions, but kids and juveniles
+    /// only face their own division.
+    pub fn can_face(self, other: AgeDivision) -> bool {
+        let open = |division| !matches!(division, AgeDivision::Kids | AgeDivision::Juvenile);
+        self == other || (open(self) && open(other))
+    }
+}
+
+impl fmt::Display for AgeDivision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgeDivision::Kids => write!(f, "Kids"),
+            AgeDivision::Juvenile => write!(f, "Juvenile"),
+            AgeDivision::Adult => write!(f, "Adult"),
+            AgeDivision::Master1 => write!(f, "Master 1"),
+            AgeDivision::Master2 => write!(f, "Master 2"),
+            AgeDivision::Master3 => write!(f, "Master 3"),
+            AgeDivision::Master4 => write!(f, "Master 4"),
+            AgeDivision::Master5 => write!(f, "Master 5"),
+            AgeDivision::Master6 => write!(f, "Master 6"),
+            AgeDivision::Master7 => write!(f, "Master 7"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Uniform {
+    Gi,
+    NoGi
+}
+
+impl Uniform {
+    pub const ALL: &'static [Uniform] = &[Uniform::Gi, Uniform::NoGi];
+}
+
+impl fmt::Display for Uniform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Uniform::Gi => write!(f, "Gi"),
+            Uniform::NoGi => write!(f, "No-Gi"),
+        }
+    }
+}
+
+/// A stable identifier for an athlete, e.g. their federation membership number, used to link
+/// their matches together.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serialize, Deserialize)]
+pub struct AthleteId(pub String);
+
+impl fmt::Display for AthleteId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
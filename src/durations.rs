@@ -0,0 +1,139 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::{AgeDivision, Belt, Competitor, DurationTableError, MatchConfigError};
+
+/// The match length for an age division, optionally only for one belt.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct DurationEntry {
+    pub age_division: AgeDivision,
+    /// `None` applies to every belt without an entry of its own.
+    pub belt: Option<Belt>,
+    pub minutes: usize
+}
+
+/// Match lengths by division. The default table follows IBJJF; promotions with their own lengths
+/// can load a custom one with `load`, as long as its lengths are allowed for their divisions.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DurationTable {
+    pub entries: Vec<DurationEntry>,
+    /// Used for divisions without an entry.
+    pub default_minutes: usize
+}
+
+impl Default for DurationTable {
+    fn default() -> Self {
+        use AgeDivision::*;
+        use Belt::*;
+
+        let entry = |age_division, belt, minutes| DurationEntry { age_division, belt, minutes };
+        Self {
+            entries: vec![
+                entry(Kids, None, 4),
+                entry(Juvenile, None, 5),
+                entry(Adult, Some(White), 5),
+                entry(Adult, Some(Blue), 6),
+                entry(Adult, Some(Purple), 7),
+                entry(Adult, Some(Brown), 8),
+                entry(Adult, Some(Black), 10),
+                entry(Adult, None, 5),
+                entry(Master1, Some(Purple), 6),
+                entry(Master1, Some(Brown), 6),
+                entry(Master1, Some(Black), 6),
+                entry(Master1, None, 5),
+                entry(Master2, None, 5),
+                entry(Master3, None, 5),
+                entry(Master4, None, 5),
+                entry(Master5, None, 5),
+                entry(Master6, None, 5),
+                entry(Master7, None, 5)
+            ],
+            default_minutes: 5
+        }
+    }
+}
+
+impl DurationTable {
+    /// The match length for a belt in an age division, preferring an entry for that belt over
+    /// one for the whole division.
+    pub fn minutes(&self, age_division: AgeDivision, belt: Belt) -> usize {
+        let division = || self.entries.iter().filter(|entry| entry.age_division == age_division);
+        division()
+            .find(|entry| entry.belt == Some(belt))
+            .or_else(|| division().find(|entry| entry.belt.is_none()))
+            .map_or(self.default_minutes, |entry| entry.minutes)
+    }
+
+    /// The match length for two competitors, the shorter of their divisions' lengths if they
+    /// differ.
+    pub fn minutes_for(&self, competitor_one: &Competitor, competitor_two: &Competitor) -> usize {
+        self.minutes(competitor_one.age_division, competitor_one.belt)
+            .min(self.minutes(competitor_two.age_division, competitor_two.belt))
+    }
+
+    /// Checks every length in the table is within `AgeDivision::match_minutes_range`, including
+    /// the default for divisions without an entry, so the builder won't reject the lengths it
+    /// looks up. The error names the first bad entry.
+    pub fn validate(&self) -> Result<(), DurationTableError> {
+        let check = |age_division: AgeDivision, minutes| {
+            let range = age_division.match_minutes_range();
+            match range.contains(&minutes) {
+                true => Ok(()),
+                false => Err(MatchConfigError::DurationOutOfRange { minutes, division: age_division, min: *range.start(), max: *range.end() })
+            }
+        };
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            check(entry.age_division, entry.minutes).map_err(|error| DurationTableError::Entry { index, entry: *entry, error })?;
+        }
+        for &age_division in AgeDivision::ALL {
+            if !self.entries.iter().any(|entry| entry.age_division == age_division) {
+                check(age_division, self.default_minutes).map_err(DurationTableError::DefaultMinutes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a table from JSON, failing if it doesn't `validate`.
+    pub fn from_json(json: &str) -> Result<DurationTable, DurationTableError> {
+        let table: DurationTable = serde_json::from_str(json)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Reads a table from a JSON file. A table that can't be read or doesn't `validate` is
+    /// reported as `io::ErrorKind::InvalidData`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<DurationTable> {
+        DurationTable::from_json(&fs::read_to_string(path)?)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AgeDivision, Belt, DurationEntry, DurationTable, DurationTableError, MatchConfigError};
+
+    #[test]
+    fn test_duration_table() {
+        let durations = DurationTable::default();
+        assert_eq!(durations.minutes(AgeDivision::Adult, Belt::Black), 10);
+        assert_eq!(durations.minutes(AgeDivision::Adult, Belt::White), 5);
+        assert_eq!(durations.minutes(AgeDivision::Juvenile, Belt::Blue), 5);
+        assert!(durations.validate().is_ok());
+
+        let custom = DurationTable::from_json(r#"{"entries": [{"age_division": "Adult", "belt": null, "minutes": 3}], "default_minutes": 4}"#).unwrap();
+        assert_eq!(custom.minutes(AgeDivision::Adult, Belt::Black), 3);
+        assert_eq!(custom.minutes(AgeDivision::Master2, Belt::Black), 4);
+
+        let error = DurationTable::from_json(r#"{"entries": [{"age_division": "Kids", "belt": "Grey", "minutes": 10}], "default_minutes": 5}"#).unwrap_err();
+        assert!(matches!(error, DurationTableError::Entry {
+            index: 0,
+            entry: DurationEntry { age_division: AgeDivision::Kids, belt: Some(Belt::Grey), minutes: 10 },
+            error: MatchConfigError::DurationOutOfRange { minutes: 10, division: AgeDivision::Kids, min: 2, max: 6 }
+        }));
+        let error = DurationTable::from_json(r#"{"entries": [], "default_minutes": 7}"#).unwrap_err();
+        assert!(matches!(error, DurationTableError::DefaultMinutes(MatchConfigError::DurationOutOfRange { minutes: 7, division: AgeDivision::Kids, .. })));
+        assert!(matches!(DurationTable::from_json("[]"), Err(DurationTableError::Json(_))));
+    }
+}
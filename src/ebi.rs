@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use crate::{CompetitorNumber, MatchTime, WinCriterion};
+
+/// Number of rounds contested before cumulative escape times decide an EBI overtime.
+const ESCAPE_TIME_ROUNDS: usize = 3;
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum EbiStartPosition {
+    BackControl,
+    SpiderWeb
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum EbiOutcome {
+    /// The attacker finished the submission.
+    Submission,
+    /// The defender escaped.
+    Escape,
+    /// The defender was held until the turn clock ran out.
+    TimeExpired
+}
+
+/// A completed overtime turn. `time_millis` is how long the turn lasted.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct EbiTurn {
+    pub attacker: CompetitorNumber,
+    pub position: EbiStartPosition,
+    pub outcome: EbiOutcome,
+    pub time_millis: usize
+}
+
+/// EBI-style overtime: competitors alternate attacking from back control or spider-web, each turn
+/// on its own clock. A round is one turn each.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EbiOvertime {
+    turns: Vec<EbiTurn>,
+    current: Option<(CompetitorNumber, EbiStartPosition)>,
+    turn_duration_millis: usize,
+    pub time: MatchTime
+}
+
+impl EbiOvertime {
+    /// Creates the overtime with `time` as the clock for each turn.
+    pub fn new(time: MatchTime) -> EbiOvertime {
+        EbiOvertime {
+            turns: Vec::new(),
+            current: None,
+            turn_duration_millis: time.get_remaining_time_milliseconds(),
+            time
+        }
+    }
+
+    pub fn turns(&self) -> &[EbiTurn] {
+        &self.turns
+    }
+
+    /// The round currently being contested, starting from 1.
+    pub fn round(&self) -> usize {
+        self.turns.len() / 2 + 1
+    }
+
+    /// The competitor attacking in the next (or current) turn. Competitor one attacks first.
+    pub fn attacker(&self) -> CompetitorNumber {
+        match self.turns.len() % 2 {
+            0 => CompetitorNumber::One,
+            _ => CompetitorNumber::Two
+        }
+    }
+
+    pub fn turn_in_progress(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Starts the next turn with a fresh clock.
+    pub fn start_turn(&mut self, position: EbiStartPosition) {
+        if self.current.is_some() || self.winner().is_some() {
+            return;
+        }
+
+        self.current = Some((self.attacker(), position));
+        self.time = self.time.fresh(self.turn_duration_millis);
+        self.time.start();
+    }
+
+    /// Ends the current turn, recording the time on the turn clock.
+    pub fn end_turn(&mut self, outcome: EbiOutcome) {
+        let Some((attacker, position)) = self.current.take() else {
+            return;
+        };
+
+        self.time.stop();
+        let time_millis = match outcome {
+            EbiOutcome::TimeExpired => self.turn_duration_millis,
+            _ => self.time.get_elapsed_time_milliseconds().min(self.turn_duration_millis)
+        };
+
+        self.turns.push(EbiTurn { attacker, position, outcome, time_millis });
+    }
+
+    /// Total time the competitor has spent defending across all completed turns.
+    pub fn cumulative_escape_millis(&self, competitor: CompetitorNumber) -> usize {
+        self.turns
+            .iter()
+            .filter(|turn| turn.attacker == competitor.opponent())
+            .map(|turn| turn.time_millis)
+            .sum()
+    }
+
+    /// Decides the overtime from completed rounds: a submission beats no submission and the
+    /// faster of two submissions wins. After the escape-time rounds, the competitor with the lower
+    /// cumulative escape time wins.
+    pub fn winner(&self) -> Option<(CompetitorNumber, WinCriterion)> {
+        for (index, round) in self.turns.chunks_exact(2).enumerate() {
+            let submissions: Vec<&EbiTurn> = round.iter().filter(|turn| turn.outcome == EbiOutcome::Submission).collect();
+
+            match submissions.as_slice() {
+                [turn] => return Some((turn.attacker, WinCriterion::OvertimeSubmission)),
+                [first, second] if first.time_millis != second.time_millis => {
+                    let fastest = if first.time_millis < second.time_millis { first } else { second };
+                    return Some((fastest.attacker, WinCriterion::OvertimeSubmission));
+                }
+                _ => {}
+            }
+
+            if index + 1 >= ESCAPE_TIME_ROUNDS && submissions.is_empty() {
+                let one = self.escape_millis_through(CompetitorNumber::One, index);
+                let two = self.escape_millis_through(CompetitorNumber::Two, index);
+
+                if one != two {
+                    let winner = if one < two { CompetitorNumber::One } else { CompetitorNumber::Two };
+                    return Some((winner, WinCriterion::OvertimeEscape));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn escape_millis_through(&self, competitor: CompetitorNumber, round_index: usize) -> usize {
+        self.turns[..(round_index + 1) * 2]
+            .iter()
+            .filter(|turn| turn.attacker == competitor.opponent())
+            .map(|turn| turn.time_millis)
+            .sum()
+    }
+}
@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt;
+use crate::{AgeDivision, CompetitorNumber, DurationEntry, MatchState, ScoreField};
+
+/// Why a match operation was refused. Returned by the `try_` variants of the `BJJMatch` methods.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchError {
+    /// The operation isn't allowed in the match's current state, e.g. scoring after the match
+    /// finished or starting a match that already started.
+    InvalidState(MatchState),
+    /// Subtracting would take the score below zero.
+    BelowZero(ScoreField),
+    /// The rule set doesn't score this field, e.g. advantages under ADCC rules.
+    FieldNotUsed(ScoreField),
+    /// Points can't be scored right now, e.g. in the first half of an ADCC match.
+    PointsNotAllowed,
+    NegativePointsNotAllowed,
+    /// The rule set has no overtime, or no further overtime.
+    NoOvertime,
+    EbiTurnInProgress,
+    NoEbiTurn,
+    TimeoutAlreadyActive,
+    NoActiveTimeout,
+    NothingToUndo,
+    NothingToRedo
+}
+
+impl fmt::Display for MatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchError::InvalidState(state) => write!(f, "Not allowed while the match state is {}", state),
+            MatchError::BelowZero(field) => write!(f, "{} can't go below zero", field),
+            MatchError::FieldNotUsed(field) => write!(f, "{} aren't used by this rule set", field),
+            MatchError::PointsNotAllowed => write!(f, "Points can't be scored in this period"),
+            MatchError::NegativePointsNotAllowed => write!(f, "Negative points aren't used by this rule set"),
+            MatchError::NoOvertime => write!(f, "No overtime left under this rule set"),
+            MatchError::EbiTurnInProgress => write!(f, "An EBI overtime turn is already in progress"),
+            MatchError::NoEbiTurn => write!(f, "No EBI overtime turn is in progress"),
+            MatchError::TimeoutAlreadyActive => write!(f, "That time-out is already running"),
+            MatchError::NoActiveTimeout => write!(f, "No time-out is running"),
+            MatchError::NothingToUndo => write!(f, "Nothing to undo"),
+            MatchError::NothingToRedo => write!(f, "Nothing to redo")
+        }
+    }
+}
+
+impl Error for MatchError {}
+
+/// Why a match couldn't be created. Returned by `MatchBuilder::build`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchConfigError {
+    /// The competitor's first or last name is blank.
+    MissingName(CompetitorNumber),
+    /// Both competitors are the same athlete, going by athlete ID or name and team.
+    SameCompetitor,
+    /// The competitors' age divisions can't be matched, e.g. a juvenile against an adult.
+    MixedAgeDivisions(AgeDivision, AgeDivision),
+    /// The match duration is outside the range allowed for a competitor's age division.
+    DurationOutOfRange { minutes: usize, division: AgeDivision, min: usize, max: usize },
+    /// Mats are numbered from 1 to `mat_count`.
+    InvalidMat { mat_number: usize, mat_count: usize },
+    /// Fights are numbered from 1.
+    InvalidFightNumber,
+    /// A mercy rule needs a margin of at least one point.
+    InvalidMercyMargin
+}
+
+impl fmt::Display for MatchConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchConfigError::MissingName(competitor) => write!(f, "Competitor {:?} needs a first and last name", competitor),
+            MatchConfigError::SameCompetitor => write!(f, "Both competitors are the same athlete"),
+            MatchConfigError::MixedAgeDivisions(one, two) => write!(f, "{} and {} competitors can't be matched against each other", one, two),
+            MatchConfigError::DurationOutOfRange { minutes, division, min, max } =>
+                write!(f, "A {} minute match is outside the {} to {} minutes allowed for {} competitors", minutes, min, max, division),
+            MatchConfigError::InvalidMat { mat_number, mat_count } => write!(f, "Mat {} doesn't exist, mats are numbered 1 to {}", mat_number, mat_count),
+            MatchConfigError::InvalidFightNumber => write!(f, "Fight numbers start at 1"),
+            MatchConfigError::InvalidMercyMargin => write!(f, "The mercy rule margin must be at least 1 point")
+        }
+    }
+}
+
+impl Error for MatchConfigError {}
+
+/// Why a duration table was rejected. Returned by `DurationTable::validate` and
+/// `DurationTable::from_json`.
+#[derive(Debug)]
+pub enum DurationTableError {
+    /// The JSON isn't a duration table.
+    Json(serde_json::Error),
+    /// The entry at `index`, counting from 0, has a length outside its division's range.
+    Entry { index: usize, entry: DurationEntry, error: MatchConfigError },
+    /// The default length is outside the range of a division that has no entry.
+    DefaultMinutes(MatchConfigError)
+}
+
+impl fmt::Display for DurationTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationTableError::Json(error) => write!(f, "{}", error),
+            DurationTableError::Entry { index, entry, error } => match entry.belt {
+                Some(belt) => write!(f, "Entry {} ({} {} Belt): {}", index + 1, entry.age_division, belt, error),
+                None => write!(f, "Entry {} ({}): {}", index + 1, entry.age_division, error)
+            },
+            DurationTableError::DefaultMinutes(error) => write!(f, "Default minutes: {}", error)
+        }
+    }
+}
+
+impl Error for DurationTableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DurationTableError::Json(error) => Some(error),
+            DurationTableError::Entry { error, .. } | DurationTableError::DefaultMinutes(error) => Some(error)
+        }
+    }
+}
+
+impl From<serde_json::Error> for DurationTableError {
+    fn from(error: serde_json::Error) -> Self {
+        DurationTableError::Json(error)
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use crate::{CompetitorNumber, EbiOutcome, EbiStartPosition, FinishMethod, Infraction, InfractionConsequence, MatchPeriod, ScoringAction, TimeoutKind};
+
+/// Something that happened during a match.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum MatchAction {
+    MatchStarted,
+    ClockStarted,
+    ClockStopped,
+    TimeAdded(usize),
+    TimeSubtracted(usize),
+    RemainingTimeSet(usize),
+    ClockReset,
+    TimeoutStarted(TimeoutKind),
+    TimeoutEnded(TimeoutKind),
+    Points(usize),
+    Scored { action: ScoringAction, points: usize },
+    NegativePoint,
+    Advantage,
+    Penalty,
+    /// An infraction was called, followed by `Penalty` if it counted as one.
+    Infraction { infraction: Infraction, consequence: InfractionConsequence },
+    PointSubtracted,
+    AdvantageSubtracted,
+    PenaltySubtracted,
+    RefereeDecision,
+    Finished(FinishMethod),
+    GoldenScore,
+    OvertimeStarted,
+    EbiTurnStarted(EbiStartPosition),
+    EbiTurnEnded(EbiOutcome),
+    Undo,
+    Redo
+}
+
+/// An entry in a match's event log. `clock_millis` is the time elapsed on the match clock (or the
+/// EBI turn clock) when the action happened.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MatchEvent {
+    pub clock_millis: usize,
+    pub period: MatchPeriod,
+    pub wall_time: SystemTime,
+    pub competitor: Option<CompetitorNumber>,
+    pub action: MatchAction
+}
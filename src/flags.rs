@@ -0,0 +1,95 @@
+use crate::Country;
+
+/// The bundled flags, for the nations that most often send competitors to international IBJJF
+/// and ADCC events. Every other country shows its country code instead.
+const FLAGS: &[(Country, &[u8])] = &[
+    (Country::Argentina, include_bytes!("../assets/flags/ar.png")),
+    (Country::Armenia, include_bytes!("../assets/flags/am.png")),
+    (Country::Australia, include_bytes!("../assets/flags/au.png")),
+    (Country::Austria, include_bytes!("../assets/flags/at.png")),
+    (Country::Belgium, include_bytes!("../assets/flags/be.png")),
+    (Country::Bolivia, include_bytes!("../assets/flags/bo.png")),
+    (Country::Brazil, include_bytes!("../assets/flags/br.png")),
+    (Country::Bulgaria, include_bytes!("../assets/flags/bg.png")),
+    (Country::Canada, include_bytes!("../assets/flags/ca.png")),
+    (Country::Chile, include_bytes!("../assets/flags/cl.png")),
+    (Country::China, include_bytes!("../assets/flags/cn.png")),
+    (Country::Colombia, include_bytes!("../assets/flags/co.png")),
+    (Country::Czechia, include_bytes!("../assets/flags/cz.png")),
+    (Country::Denmark, include_bytes!("../assets/flags/dk.png")),
+    (Country::Estonia, include_bytes!("../assets/flags/ee.png")),
+    (Country::Finland, include_bytes!("../assets/flags/fi.png")),
+    (Country::France, include_bytes!("../assets/flags/fr.png")),
+    (Country::Germany, include_bytes!("../assets/flags/de.png")),
+    (Country::Greece, include_bytes!("../assets/flags/gr.png")),
+    (Country::Hungary, include_bytes!("../assets/flags/hu.png")),
+    (Country::Iceland, include_bytes!("../assets/flags/is.png")),
+    (Country::India, include_bytes!("../assets/flags/in.png")),
+    (Country::Indonesia, include_bytes!("../assets/flags/id.png")),
+    (Country::Ireland, include_bytes!("../assets/flags/ie.png")),
+    (Country::Israel, include_bytes!("../assets/flags/il.png")),
+    (Country::Italy, include_bytes!("../assets/flags/it.png")),
+    (Country::Japan, include_bytes!("../assets/flags/jp.png")),
+    (Country::Latvia, include_bytes!("../assets/flags/lv.png")),
+    (Country::Lithuania, include_bytes!("../assets/flags/lt.png")),
+    (Country::Luxembourg, include_bytes!("../assets/flags/lu.png")),
+    (Country::Mexico, include_bytes!("../assets/flags/mx.png")),
+    (Country::Netherlands, include_bytes!("../assets/flags/nl.png")),
+    (Country::NewZealand, include_bytes!("../assets/flags/nz.png")),
+    (Country::Norway, include_bytes!("../assets/flags/no.png")),
+    (Country::Panama, include_bytes!("../assets/flags/pa.png")),
+    (Country::Peru, include_bytes!("../assets/flags/pe.png")),
+    (Country::Philippines, include_bytes!("../assets/flags/ph.png")),
+    (Country::Poland, include_bytes!("../assets/flags/pl.png")),
+    (Country::Portugal, include_bytes!("../assets/flags/pt.png")),
+    (Country::Romania, include_bytes!("../assets/flags/ro.png")),
+    (Country::Russia, include_bytes!("../assets/flags/ru.png")),
+    (Country::SouthAfrica, include_bytes!("../assets/flags/za.png")),
+    (Country::SouthKorea, include_bytes!("../assets/flags/kr.png")),
+    (Country::Spain, include_bytes!("../assets/flags/es.png")),
+    (Country::Sweden, include_bytes!("../assets/flags/se.png")),
+    (Country::Switzerland, include_bytes!("../assets/flags/ch.png")),
+    (Country::Thailand, include_bytes!("../assets/flags/th.png")),
+    (Country::Turkiye, include_bytes!("../assets/flags/tr.png")),
+    (Country::Ukraine, include_bytes!("../assets/flags/ua.png")),
+    (Country::UnitedArabEmirates, include_bytes!("../assets/flags/ae.png")),
+    (Country::UnitedKingdom, include_bytes!("../assets/flags/gb.png")),
+    (Country::UnitedStates, include_bytes!("../assets/flags/us.png")),
+    (Country::Vietnam, include_bytes!("../assets/flags/vn.png")),
+];
+
+impl Country {
+    /// The bundled flag image for the country as PNG bytes, if there is one.
+    pub fn flag_png(self) -> Option<&'static [u8]> {
+        FLAGS.iter().find(|(country, _)| *country == self).map(|(_, png)| *png)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Country;
+
+    #[test]
+    fn test_flags() {
+        for png in Country::ALL.iter().filter_map(|country| country.flag_png()) {
+            assert!(png.starts_with(b"\x89PNG"));
+        }
+
+        for country in [
+            Country::Brazil, Country::UnitedStates, Country::Japan, Country::Australia, Country::UnitedKingdom,
+            Country::Portugal, Country::Canada, Country::Mexico, Country::UnitedArabEmirates, Country::Russia
+        ] {
+            assert!(country.flag_png().is_some(), "{} has no flag", country);
+        }
+        for country in [Country::Chad, Country::Palau, Country::Antarctica, Country::Tuvalu] {
+            assert_eq!(country.flag_png(), None);
+        }
+
+        // Brazil's flag has the blue globe crossed by the white motto band.
+        let brazil = image::load_from_memory(Country::Brazil.flag_png().unwrap()).unwrap().to_rgb8();
+        let (width, height) = brazil.dimensions();
+        assert_eq!(brazil.get_pixel(width / 2, height / 2).0, [0, 39, 118]);
+        let band_row = height * 6 / 14;
+        assert!((width * 2 / 5..width * 3 / 5).any(|x| brazil.get_pixel(x, band_row).0 == [255, 255, 255]));
+    }
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Why a referee sanctioned a competitor.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Infraction {
+    /// Stalling or lack of combativeness.
+    Stalling,
+    /// Leaving the mat, e.g. to escape a submission or sweep.
+    LeavingTheMat,
+    IllegalTechnique,
+    /// A serious foul, e.g. striking, biting or unsportsmanlike conduct.
+    SeriousFoul
+}
+
+impl Infraction {
+    pub const ALL: [Infraction; 4] = [
+        Infraction::Stalling,
+        Infraction::LeavingTheMat,
+        Infraction::IllegalTechnique,
+        Infraction::SeriousFoul
+    ];
+}
+
+impl fmt::Display for Infraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Infraction::Stalling => write!(f, "Stalling"),
+            Infraction::LeavingTheMat => write!(f, "Leaving the Mat"),
+            Infraction::IllegalTechnique => write!(f, "Illegal Technique"),
+            Infraction::SeriousFoul => write!(f, "Serious Foul")
+        }
+    }
+}
+
+/// What an infraction costs the competitor, as decided by the rule set.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum InfractionConsequence {
+    /// A warning that doesn't count towards the competitor's penalties.
+    Warning,
+    /// A penalty, with whatever the rule set's penalty consequences give for the new total.
+    Penalty,
+    /// A penalty, and points for the opponent on top.
+    PenaltyAndOpponentPoints(usize),
+    Disqualification
+}
@@ -1,54 +1,316 @@
-use std::time::{Duration, SystemTime};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq)]
+pub mod builder;
+pub mod clock;
+pub mod country;
+pub mod division;
+pub mod durations;
+pub mod ebi;
+pub mod error;
+pub mod events;
+pub mod infractions;
+mod flags;
+pub mod rules;
+pub mod timeouts;
+pub mod timer_events;
+
+pub use builder::MatchBuilder;
+pub use clock::{Clock, MockClock, MonotonicClock};
+pub use country::{Country, UnknownCountryCode};
+pub use division::{AgeDivision, AthleteId, Belt, Uniform, WeightClass};
+pub use durations::{DurationEntry, DurationTable};
+pub use ebi::{EbiOutcome, EbiOvertime, EbiStartPosition, EbiTurn};
+pub use error::{DurationTableError, MatchConfigError, MatchError};
+pub use events::{MatchAction, MatchEvent};
+pub use infractions::{Infraction, InfractionConsequence};
+pub use rules::{MatchPeriod, Overtime, PenaltyConsequence, RuleSet, RuleSetConfig};
+pub use timeouts::{CompetitorTimeouts, MatchTimeouts, TimeoutKind, TimeoutLimits};
+pub use timer_events::{TimerEvent, TimerObserver, TimerWatch};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum CompetitorNumber {
     One,
     Two
 }
 
-#[derive(Debug, PartialEq)]
+impl CompetitorNumber {
+    pub fn opponent(self) -> CompetitorNumber {
+        match self {
+            CompetitorNumber::One => CompetitorNumber::Two,
+            CompetitorNumber::Two => CompetitorNumber::One
+        }
+    }
+}
+
+/// Where a match is in its lifecycle.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum MatchState {
     NotStarted,
-    InProgress,
-    Finished
+    Running,
+    /// The clock is stopped during regulation or overtime.
+    Paused,
+    /// A competitor's injury, blood or equipment time-out is running.
+    InjuryTimeout,
+    /// The overtime clock is running, or EBI overtime is being contested.
+    Overtime,
+    /// Time has run out with the scores level and the referee has not given a decision yet.
+    AwaitingDecision,
+    Finished(MatchResult)
 }
 
-#[derive(Debug, PartialEq)]
-enum ScoreField {
+impl fmt::Display for MatchState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchState::NotStarted => write!(f, "Not Started"),
+            MatchState::Running => write!(f, "Running"),
+            MatchState::Paused => write!(f, "Paused"),
+            MatchState::InjuryTimeout => write!(f, "Time-out"),
+            MatchState::Overtime => write!(f, "Overtime"),
+            MatchState::AwaitingDecision => write!(f, "Awaiting Decision"),
+            MatchState::Finished(_) => write!(f, "Finished")
+        }
+    }
+}
+
+impl MatchState {
+    /// Whether points, advantages and penalties can be changed in this state.
+    pub fn allows_scoring(&self) -> bool {
+        matches!(self, MatchState::Running | MatchState::Paused | MatchState::InjuryTimeout | MatchState::Overtime)
+    }
+
+    /// Whether a match can move directly from this state to `next`.
+    pub fn can_transition_to(&self, next: &MatchState) -> bool {
+        use MatchState::*;
+
+        match (self, next) {
+            (Finished(_), _) => false,
+            (_, Finished(_)) => true,
+            (NotStarted, Running) => true,
+            (Running, Paused | InjuryTimeout | AwaitingDecision) => true,
+            (Paused, Running | Overtime | InjuryTimeout) => true,
+            (InjuryTimeout, Running | Paused | Overtime) => true,
+            (Overtime, Paused | InjuryTimeout | AwaitingDecision) => true,
+            (AwaitingDecision, Overtime) => true,
+            _ => false
+        }
+    }
+}
+
+/// Ways a match can be ended before the clock runs out.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum FinishMethod {
+    Submission(String),
+    Disqualification,
+    Injury,
+    Walkover,
+    /// The winner's lead on points reached the mercy rule margin.
+    PointsMargin
+}
+
+/// The criterion that decided a finished match. Matches that go the distance are decided by
+/// points, then advantages, then penalties, then referee decision.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum WinCriterion {
+    Points,
+    Advantages,
+    Penalties,
+    RefereeDecision,
+    Finish(FinishMethod),
+    OvertimeSubmission,
+    OvertimeEscape,
+    GoldenScore
+}
+
+impl fmt::Display for WinCriterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WinCriterion::Points => write!(f, "Points"),
+            WinCriterion::Advantages => write!(f, "Advantages"),
+            WinCriterion::Penalties => write!(f, "Penalties"),
+            WinCriterion::RefereeDecision => write!(f, "Referee Decision"),
+            WinCriterion::Finish(FinishMethod::Submission(name)) => write!(f, "Submission ({})", name),
+            WinCriterion::Finish(FinishMethod::Disqualification) => write!(f, "Disqualification"),
+            WinCriterion::Finish(FinishMethod::Injury) => write!(f, "Injury"),
+            WinCriterion::Finish(FinishMethod::Walkover) => write!(f, "Walkover"),
+            WinCriterion::Finish(FinishMethod::PointsMargin) => write!(f, "Points Margin"),
+            WinCriterion::OvertimeSubmission => write!(f, "Overtime Submission"),
+            WinCriterion::OvertimeEscape => write!(f, "Overtime Escape Time"),
+            WinCriterion::GoldenScore => write!(f, "Golden Score")
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct MatchResult {
+    pub winner: CompetitorNumber,
+    pub criterion: WinCriterion
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ScoreField {
     Points,
     Advantages,
     Penalties
 }
 
-#[derive(Debug, PartialEq)]
-pub enum Country {
-    Australia,
-    Brazil,
-    UnitedStates
+impl fmt::Display for ScoreField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreField::Points => write!(f, "Points"),
+            ScoreField::Advantages => write!(f, "Advantages"),
+            ScoreField::Penalties => write!(f, "Penalties")
+        }
+    }
+}
+
+/// A technique that scores points. How many points it is worth depends on the rule set.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ScoringAction {
+    Takedown,
+    Sweep,
+    KneeOnBelly,
+    GuardPass,
+    Mount,
+    BackTake
+}
+
+impl ScoringAction {
+    pub const ALL: [ScoringAction; 6] = [
+        ScoringAction::Takedown,
+        ScoringAction::Sweep,
+        ScoringAction::KneeOnBelly,
+        ScoringAction::GuardPass,
+        ScoringAction::Mount,
+        ScoringAction::BackTake
+    ];
+}
+
+impl fmt::Display for ScoringAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoringAction::Takedown => write!(f, "Takedown"),
+            ScoringAction::Sweep => write!(f, "Sweep"),
+            ScoringAction::KneeOnBelly => write!(f, "Knee on Belly"),
+            ScoringAction::GuardPass => write!(f, "Guard Pass"),
+            ScoringAction::Mount => write!(f, "Mount"),
+            ScoringAction::BackTake => write!(f, "Back Take")
+        }
+    }
+}
+
+/// Points scored by a single technique. `action` is `None` for points that didn't come from a
+/// technique: points added by hand, points a penalty gave the opponent and negative points.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct ScoreRecord {
+    pub action: Option<ScoringAction>,
+    pub points: isize
+}
+
+/// How often a technique scored and the total points it produced. `action` is `None` for the
+/// points that didn't come from a technique.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct TechniqueBreakdown {
+    pub action: Option<ScoringAction>,
+    pub count: usize,
+    pub points: isize
 }
 
-#[derive(Default ,Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct MatchScore {
     pub competitor_one_score: PlayerScore,
     pub competitor_two_score: PlayerScore,
     pub time_remaining_milliseconds: usize,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerScore {
-    pub points: usize,
+    pub points: isize,
     pub advantages: usize,
-    pub penalties: usize
+    pub penalties: usize,
+    pub scoring_actions: Vec<ScoreRecord>,
+    /// Every infraction called against the competitor, oldest first, including warnings that
+    /// didn't count as penalties.
+    #[serde(default)]
+    pub infractions: Vec<Infraction>,
+    /// The infraction behind each penalty, oldest first, or `None` for a penalty given directly.
+    #[serde(default)]
+    penalty_causes: Vec<Option<Infraction>>
 }
 
 impl PlayerScore {
-    fn subtract(&mut self, field: ScoreField) {
-        match field {
-            ScoreField::Points => {
-                if self.points > 0 {
-                    self.points -= 1;
+    pub fn infraction_count(&self, infraction: Infraction) -> usize {
+        self.infractions.iter().filter(|called| **called == infraction).count()
+    }
+
+    /// Forgets the cause of the latest penalty, taking its infraction, if any, off the record.
+    /// Matches saved before causes were kept have none to forget.
+    fn pop_penalty_cause(&mut self) -> Option<Infraction> {
+        if self.penalty_causes.len() != self.penalties {
+            return None;
+        }
+
+        let infraction = self.penalty_causes.pop().flatten()?;
+        let index = self.infractions.iter().rposition(|called| *called == infraction)?;
+        self.infractions.remove(index);
+        Some(infraction)
+    }
+
+    /// Per-technique totals of the points scored, in `ScoringAction::ALL` order, followed by the
+    /// points that didn't come from a technique. The totals add up to `points`.
+    pub fn technique_breakdown(&self) -> Vec<TechniqueBreakdown> {
+        ScoringAction::ALL
+            .iter()
+            .map(|action| Some(*action))
+            .chain([None])
+            .filter_map(|action| {
+                let records: Vec<&ScoreRecord> = self.scoring_actions.iter().filter(|record| record.action == action).collect();
+                match records.len() {
+                    0 => None,
+                    count => Some(TechniqueBreakdown {
+                        action,
+                        count,
+                        points: records.iter().map(|record| record.points).sum()
+                    })
                 }
+            })
+            .collect()
+    }
+
+    /// Adds (or with negative `points`, takes away) points, recording where they came from.
+    fn record_points(&mut self, action: Option<ScoringAction>, points: isize) {
+        self.points += points;
+        self.scoring_actions.push(ScoreRecord { action, points });
+    }
+
+    /// Takes away up to `points` points without going below zero, taking them off the most
+    /// recent scores first so the breakdown still adds up.
+    fn remove_points(&mut self, points: usize) {
+        let mut remaining = (points as isize).min(self.points.max(0));
+        self.points -= remaining;
+        while remaining > 0 {
+            let Some(index) = self.scoring_actions.iter().rposition(|record| record.points > 0) else {
+                break;
+            };
+            let record = &mut self.scoring_actions[index];
+            let taken = record.points.min(remaining);
+            record.points -= taken;
+            remaining -= taken;
+            if record.points == 0 {
+                self.scoring_actions.remove(index);
             }
+        }
+    }
+
+    fn subtract(&mut self, field: ScoreField) {
+        match field {
+            ScoreField::Points => self.remove_points(1),
             ScoreField::Advantages => {
                 if self.advantages > 0 {
                     self.advantages -= 1;
@@ -63,14 +325,43 @@ impl PlayerScore {
     }
 }
 
-#[derive(Default, Debug)]
+/// The parts of a match that undo and redo restore.
+#[derive(Debug, Clone)]
+struct MatchSnapshot {
+    score: MatchScore,
+    time: MatchTime,
+    referee_decision: Option<CompetitorNumber>,
+    finish: Option<MatchResult>,
+    period: MatchPeriod,
+    ebi: Option<EbiOvertime>,
+    timeouts: MatchTimeouts
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct BJJMatch {
     pub info: MatchInformation,
     pub score: MatchScore,
-    pub time: MatchTime
+    pub time: MatchTime,
+    referee_decision: Option<CompetitorNumber>,
+    finish: Option<MatchResult>,
+    #[serde(with = "rules::by_config")]
+    rules: Box<dyn RuleSet>,
+    period: MatchPeriod,
+    ebi: Option<EbiOvertime>,
+    timeouts: MatchTimeouts,
+    events: Vec<MatchEvent>,
+    #[serde(default)]
+    mercy_margin: Option<usize>,
+    #[serde(skip)]
+    undo_stack: Vec<MatchSnapshot>,
+    #[serde(skip)]
+    redo_stack: Vec<MatchSnapshot>,
+    #[serde(skip)]
+    timer_watch: TimerWatch
 }
 
 impl BJJMatch {
+    /// Creates a match without checking its configuration. Front ends should use `builder()`.
     pub fn new(competitor_one: Competitor, competitor_two: Competitor, match_time_minutes: usize, mat_number: usize, fight_number: usize) -> BJJMatch {
         BJJMatch{
             info: MatchInformation {
@@ -81,76 +372,1011 @@ impl BJJMatch {
                 fight_number
             },
             score: MatchScore::default(),
-            time: MatchTime {
-                duration_millis: match_time_minutes * 60 * 1000,
-                ..Default::default()
-            }
+            time: MatchTime::new(match_time_minutes * 60 * 1000),
+            referee_decision: None,
+            finish: None,
+            rules: Box::default(),
+            period: MatchPeriod::Regulation,
+            ebi: None,
+            timeouts: MatchTimeouts::default(),
+            events: Vec::new(),
+            mercy_margin: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            timer_watch: TimerWatch::default()
         }
     }
 
+    pub fn builder() -> MatchBuilder {
+        MatchBuilder::default()
+    }
+
+    /// Replaces the rule set the match is scored under. Matches use IBJJF rules by default.
+    pub fn with_rules(mut self, rules: Box<dyn RuleSet>) -> BJJMatch {
+        self.rules = rules;
+        self
+    }
+
+    /// Drives the match clock from the given clock instead of the system's monotonic clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> BJJMatch {
+        self.time = MatchTime::with_clock(self.time.duration_millis, clock);
+        let limits = self.timeouts.limits;
+        self.timeouts = MatchTimeouts::new(&self.time);
+        self.timeouts.limits = limits;
+        self
+    }
+
+    /// Ends the match as soon as either competitor leads by `margin` points or more, as some kids
+    /// and novice events do. A margin of 0 would end the match on its first score, so it leaves
+    /// the mercy rule off.
+    pub fn with_mercy_rule(mut self, margin: usize) -> BJJMatch {
+        self.set_mercy_rule(Some(margin));
+        self
+    }
+
+    /// Sets or, with `None`, removes the mercy rule's points margin. As with `with_mercy_rule`, a
+    /// margin of 0 removes it.
+    pub fn set_mercy_rule(&mut self, margin: Option<usize>) {
+        self.mercy_margin = margin.filter(|&margin| margin > 0);
+    }
+
+    pub fn mercy_rule(&self) -> Option<usize> {
+        self.mercy_margin
+    }
+
+    pub fn set_rules(&mut self, rules: Box<dyn RuleSet>) {
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> &dyn RuleSet {
+        self.rules.as_ref()
+    }
+
+    /// Hands the rule set over, e.g. to a match built from this one's settings, leaving the
+    /// default in its place.
+    pub fn take_rules(&mut self) -> Box<dyn RuleSet> {
+        std::mem::take(&mut self.rules)
+    }
+
     pub fn get_match_state(&self) -> MatchState {
-        match self.time.last_started {
-            None => MatchState::NotStarted,
-            _ => match self.time.get_remaining_time_milliseconds() {
-                0 => MatchState::Finished,
-                _ => MatchState::InProgress,
-            },
+        if let Some(result) = self.stoppage_result() {
+            return MatchState::Finished(result);
+        }
+
+        if self.ebi.is_some() {
+            return MatchState::Overtime;
+        }
+
+        if self.timeouts.active().is_some() {
+            return MatchState::InjuryTimeout;
+        }
+
+        if self.time.last_started.is_none() && self.period == MatchPeriod::Regulation {
+            return MatchState::NotStarted;
+        }
+
+        if self.time.get_remaining_time_milliseconds() == 0 {
+            return match self.decision() {
+                Some(result) => MatchState::Finished(result),
+                None => MatchState::AwaitingDecision
+            };
+        }
+
+        match (self.time.is_running(), self.period) {
+            (false, _) => MatchState::Paused,
+            (true, MatchPeriod::Regulation) => MatchState::Running,
+            (true, MatchPeriod::Overtime(_)) => MatchState::Overtime
         }
     }
 
-    pub fn add_points(&mut self, points: usize, competitor: CompetitorNumber) {
+    /// Checks that the match can move from its current state to `next`.
+    fn ensure_can_enter(&self, next: &MatchState) -> Result<(), MatchError> {
+        let current = self.get_match_state();
+        match current == *next || current.can_transition_to(next) {
+            true => Ok(()),
+            false => Err(MatchError::InvalidState(current))
+        }
+    }
+
+    fn ensure_scoring_allowed(&self) -> Result<(), MatchError> {
+        let state = self.get_match_state();
+        match state.allows_scoring() {
+            true => Ok(()),
+            false => Err(MatchError::InvalidState(state))
+        }
+    }
+
+    fn ensure_state(&self, expected: MatchState) -> Result<(), MatchError> {
+        let state = self.get_match_state();
+        match state == expected {
+            true => Ok(()),
+            false => Err(MatchError::InvalidState(state))
+        }
+    }
+
+    pub fn competitor(&self, competitor: CompetitorNumber) -> &Competitor {
         match competitor {
-            CompetitorNumber::One => self.score.competitor_one_score.points += points,
-            CompetitorNumber::Two => self.score.competitor_two_score.points += points
+            CompetitorNumber::One => &self.info.competitor_one,
+            CompetitorNumber::Two => &self.info.competitor_two
+        }
+    }
+
+    /// Returns the result of a finished match, or `None` while the match is still going or when
+    /// the scores are level and the referee has not yet given a decision.
+    pub fn result(&self) -> Option<MatchResult> {
+        match self.get_match_state() {
+            MatchState::Finished(result) => Some(result),
+            _ => None
+        }
+    }
+
+    /// The result of a match ended before time ran out, by a finish, an exhausted time-out
+    /// allowance or EBI overtime.
+    fn stoppage_result(&self) -> Option<MatchResult> {
+        if let Some(result) = &self.finish {
+            return Some(result.clone());
+        }
+
+        if let Some(injured) = self.timeouts.exceeded() {
+            return Some(MatchResult { winner: injured.opponent(), criterion: WinCriterion::Finish(FinishMethod::Injury) });
+        }
+
+        self.ebi.as_ref()?.winner().map(|(winner, criterion)| MatchResult { winner, criterion })
+    }
+
+    /// Decides a match that went the distance using the rule set's tie-breakers, e.g. points,
+    /// then advantages, then fewer penalties, then referee decision under IBJJF rules.
+    fn decision(&self) -> Option<MatchResult> {
+        let one = &self.score.competitor_one_score;
+        let two = &self.score.competitor_two_score;
+
+        for criterion in self.rules.tie_breakers() {
+            let ordering = match criterion {
+                WinCriterion::Points => one.points.cmp(&two.points),
+                WinCriterion::Advantages => one.advantages.cmp(&two.advantages),
+                WinCriterion::Penalties => two.penalties.cmp(&one.penalties),
+                WinCriterion::RefereeDecision => {
+                    return self.referee_decision.map(|winner| MatchResult { winner, criterion });
+                }
+                WinCriterion::Finish(_)
+                | WinCriterion::OvertimeSubmission
+                | WinCriterion::OvertimeEscape
+                | WinCriterion::GoldenScore => Ordering::Equal
+            };
+
+            match ordering {
+                Ordering::Greater => return Some(MatchResult { winner: CompetitorNumber::One, criterion }),
+                Ordering::Less => return Some(MatchResult { winner: CompetitorNumber::Two, criterion }),
+                Ordering::Equal => {}
+            }
+        }
+
+        None
+    }
+
+    /// Records the referee's decision, used only when the scores are level at the end of the match.
+    pub fn set_referee_decision(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_set_referee_decision(competitor);
+    }
+
+    pub fn try_set_referee_decision(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_state(MatchState::AwaitingDecision)?;
+
+        self.push_undo();
+        self.referee_decision = Some(competitor);
+        self.log(Some(competitor), MatchAction::RefereeDecision);
+        Ok(())
+    }
+
+    /// Ends the match immediately in favour of `winner`, freezing the clock.
+    pub fn finish(&mut self, winner: CompetitorNumber, method: FinishMethod) {
+        let _ = self.try_finish(winner, method);
+    }
+
+    pub fn try_finish(&mut self, winner: CompetitorNumber, method: FinishMethod) -> Result<(), MatchError> {
+        let state = self.get_match_state();
+        if matches!(state, MatchState::Finished(_)) {
+            return Err(MatchError::InvalidState(state));
+        }
+
+        self.push_undo();
+        self.finish_match(winner, method);
+        Ok(())
+    }
+
+    fn finish_match(&mut self, winner: CompetitorNumber, method: FinishMethod) {
+        self.time.stop();
+        self.finish = Some(MatchResult { winner, criterion: WinCriterion::Finish(method.clone()) });
+        self.log(Some(winner), MatchAction::Finished(method));
+    }
+
+    /// Ends the match if a competitor's lead on points has reached the mercy rule margin.
+    fn check_mercy_rule(&mut self) {
+        let Some(margin) = self.mercy_margin else {
+            return;
         };
+        if self.finish.is_some() {
+            return;
+        }
+
+        let lead = self.score.competitor_one_score.points - self.score.competitor_two_score.points;
+        if lead != 0 && lead.unsigned_abs() >= margin {
+            let leader = if lead > 0 { CompetitorNumber::One } else { CompetitorNumber::Two };
+            self.finish_match(leader, FinishMethod::PointsMargin);
+        }
+    }
+
+    fn in_golden_score(&self) -> bool {
+        matches!(self.period, MatchPeriod::Overtime(_))
+            && matches!(self.rules.overtime(), Overtime::GoldenScore { .. })
+            && self.finish.is_none()
+    }
+
+    /// Ends a golden score overtime as soon as either competitor leads on points or advantages.
+    fn check_golden_score(&mut self) {
+        if !self.in_golden_score() {
+            return;
+        }
+
+        let one = &self.score.competitor_one_score;
+        let two = &self.score.competitor_two_score;
+        let winner = match one.points.cmp(&two.points).then(one.advantages.cmp(&two.advantages)) {
+            Ordering::Greater => CompetitorNumber::One,
+            Ordering::Less => CompetitorNumber::Two,
+            Ordering::Equal => return
+        };
+
+        self.time.stop();
+        self.finish = Some(MatchResult { winner, criterion: WinCriterion::GoldenScore });
+        self.log(Some(winner), MatchAction::GoldenScore);
+    }
+
+    pub fn submission(&mut self, winner: CompetitorNumber, submission_name: &str) {
+        self.finish(winner, FinishMethod::Submission(submission_name.to_owned()));
+    }
+
+    pub fn disqualify(&mut self, competitor: CompetitorNumber) {
+        self.finish(competitor.opponent(), FinishMethod::Disqualification);
+    }
+
+    pub fn medical_stoppage(&mut self, injured: CompetitorNumber) {
+        self.finish(injured.opponent(), FinishMethod::Injury);
+    }
+
+    pub fn walkover(&mut self, winner: CompetitorNumber) {
+        self.finish(winner, FinishMethod::Walkover);
+    }
+
+    pub fn period(&self) -> MatchPeriod {
+        self.period
+    }
+
+    /// Whether points can currently be scored, e.g. ADCC rules don't score points in the first
+    /// half of regulation.
+    pub fn points_allowed(&self) -> bool {
+        self.rules.uses_field(ScoreField::Points)
+            && self.rules.points_allowed(self.period, self.time.get_elapsed_time_milliseconds(), self.time.duration_millis)
+    }
+
+    /// Starts the next overtime period when the rule set has scoring overtime periods, or EBI
+    /// overtime when the rule set uses it. Overtime only starts once time has run out on a tied
+    /// match.
+    pub fn start_overtime(&mut self) {
+        let _ = self.try_start_overtime();
+    }
+
+    pub fn try_start_overtime(&mut self) -> Result<(), MatchError> {
+        self.ensure_state(MatchState::AwaitingDecision)?;
+
+        match self.rules.overtime() {
+            Overtime::Periods { duration_millis } => {
+                self.push_undo();
+                self.period = match self.period {
+                    MatchPeriod::Regulation => MatchPeriod::Overtime(1),
+                    MatchPeriod::Overtime(period) => MatchPeriod::Overtime(period + 1)
+                };
+                self.time = self.time.fresh(duration_millis);
+                self.time.start();
+                self.log(None, MatchAction::OvertimeStarted);
+            }
+            Overtime::Ebi { turn_duration_millis } => {
+                if self.ebi.is_some() {
+                    return Err(MatchError::NoOvertime);
+                }
+
+                self.push_undo();
+                self.time.stop();
+                self.period = MatchPeriod::Overtime(1);
+                self.ebi = Some(EbiOvertime::new(self.time.fresh(turn_duration_millis)));
+                self.log(None, MatchAction::OvertimeStarted);
+            }
+            Overtime::GoldenScore { duration_millis } => {
+                if self.period != MatchPeriod::Regulation {
+                    return Err(MatchError::NoOvertime);
+                }
+
+                self.push_undo();
+                self.period = MatchPeriod::Overtime(1);
+                self.time = match duration_millis {
+                    Some(duration_millis) => self.time.fresh(duration_millis),
+                    None => self.time.fresh_count_up()
+                };
+                self.time.start();
+                self.log(None, MatchAction::OvertimeStarted);
+            }
+            Overtime::None => return Err(MatchError::NoOvertime)
+        }
+
+        Ok(())
+    }
+
+    pub fn ebi_overtime(&self) -> Option<&EbiOvertime> {
+        self.ebi.as_ref()
+    }
+
+    /// Starts the next EBI overtime turn from the attacker's chosen position.
+    pub fn start_ebi_turn(&mut self, position: EbiStartPosition) {
+        let _ = self.try_start_ebi_turn(position);
+    }
+
+    pub fn try_start_ebi_turn(&mut self, position: EbiStartPosition) -> Result<(), MatchError> {
+        let Some(ebi) = &self.ebi else {
+            return Err(MatchError::InvalidState(self.get_match_state()));
+        };
+        if ebi.winner().is_some() {
+            return Err(MatchError::InvalidState(self.get_match_state()));
+        }
+        if ebi.turn_in_progress() {
+            return Err(MatchError::EbiTurnInProgress);
+        }
+
+        self.push_undo();
+        let Some(ebi) = &mut self.ebi else {
+            return Ok(());
+        };
+        let attacker = ebi.attacker();
+        ebi.start_turn(position);
+        self.log(Some(attacker), MatchAction::EbiTurnStarted(position));
+        Ok(())
+    }
+
+    /// Ends the current EBI overtime turn and moves on to the next round once both competitors
+    /// have attacked.
+    pub fn end_ebi_turn(&mut self, outcome: EbiOutcome) {
+        let _ = self.try_end_ebi_turn(outcome);
+    }
+
+    pub fn try_end_ebi_turn(&mut self, outcome: EbiOutcome) -> Result<(), MatchError> {
+        if !self.ebi.as_ref().is_some_and(EbiOvertime::turn_in_progress) {
+            return Err(MatchError::NoEbiTurn);
+        }
+
+        self.push_undo();
+        let Some(ebi) = &mut self.ebi else {
+            return Ok(());
+        };
+        let attacker = ebi.attacker();
+        ebi.end_turn(outcome);
+        let round = ebi.round();
+        self.log(Some(attacker), MatchAction::EbiTurnEnded(outcome));
+        self.period = MatchPeriod::Overtime(round);
+        Ok(())
+    }
+
+    /// The clock currently being shown, i.e. the EBI turn clock during EBI overtime.
+    pub fn clock(&self) -> &MatchTime {
+        match &self.ebi {
+            Some(ebi) => &ebi.time,
+            None => &self.time
+        }
+    }
+
+    pub fn add_points(&mut self, points: usize, competitor: CompetitorNumber) {
+        let _ = self.try_add_points(points, competitor);
+    }
+
+    pub fn try_add_points(&mut self, points: usize, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_points_allowed()?;
+
+        self.push_undo();
+        self.score_mut(competitor).record_points(None, points as isize);
+        self.log(Some(competitor), MatchAction::Points(points));
+        self.check_mercy_rule();
+        self.check_golden_score();
+        Ok(())
+    }
+
+    /// Scores a technique for a competitor, worth the points the rule set gives it.
+    pub fn score_action(&mut self, action: ScoringAction, competitor: CompetitorNumber) {
+        let _ = self.try_score_action(action, competitor);
+    }
+
+    pub fn try_score_action(&mut self, action: ScoringAction, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_points_allowed()?;
+
+        self.push_undo();
+        let points = self.rules.points_for(action);
+        self.score_mut(competitor).record_points(Some(action), points as isize);
+        self.log(Some(competitor), MatchAction::Scored { action, points });
+        self.check_mercy_rule();
+        self.check_golden_score();
+        Ok(())
+    }
+
+    fn ensure_points_allowed(&self) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+
+        if !self.rules.uses_field(ScoreField::Points) {
+            return Err(MatchError::FieldNotUsed(ScoreField::Points));
+        }
+        if !self.points_allowed() {
+            return Err(MatchError::PointsNotAllowed);
+        }
+
+        Ok(())
+    }
+
+    /// Takes a point off a competitor as a sanction, e.g. for pulling guard or refusing to engage
+    /// under ADCC rules. Unlike `subtract_point` this can take the score below zero.
+    pub fn add_negative_point(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_add_negative_point(competitor);
+    }
+
+    pub fn try_add_negative_point(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        if !self.rules.allows_negative_points() {
+            return Err(MatchError::NegativePointsNotAllowed);
+        }
+
+        self.push_undo();
+        self.score_mut(competitor).record_points(None, -1);
+        self.log(Some(competitor), MatchAction::NegativePoint);
+        self.check_mercy_rule();
+        Ok(())
     }
 
     pub fn add_advantage(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_add_advantage(competitor);
+    }
+
+    pub fn try_add_advantage(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        if !self.rules.uses_field(ScoreField::Advantages) {
+            return Err(MatchError::FieldNotUsed(ScoreField::Advantages));
+        }
+
+        self.push_undo();
         match competitor {
             CompetitorNumber::One => self.score.competitor_one_score.advantages += 1,
             CompetitorNumber::Two => self.score.competitor_two_score.advantages += 1
         };
+        self.log(Some(competitor), MatchAction::Advantage);
+        self.check_golden_score();
+        Ok(())
     }
 
+    /// Adds a penalty and applies the rule set's escalation, e.g. under IBJJF rules the second
+    /// penalty gives the opponent an advantage, the third gives the opponent two points and the
+    /// fourth disqualifies.
     pub fn add_penalty(&mut self, competitor: CompetitorNumber) {
-        match competitor {
-            CompetitorNumber::One => self.score.competitor_one_score.penalties += 1,
-            CompetitorNumber::Two => self.score.competitor_two_score.penalties += 1
+        let _ = self.try_add_penalty(competitor);
+    }
+
+    pub fn try_add_penalty(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        if !self.rules.uses_field(ScoreField::Penalties) {
+            return Err(MatchError::FieldNotUsed(ScoreField::Penalties));
+        }
+
+        self.push_undo();
+        self.penalize(competitor, None);
+        self.check_mercy_rule();
+        self.check_golden_score();
+        Ok(())
+    }
+
+    fn penalize(&mut self, competitor: CompetitorNumber, cause: Option<Infraction>) {
+        let score = self.score_mut(competitor);
+        score.penalties += 1;
+        score.penalty_causes.push(cause);
+        let penalties = score.penalties;
+        self.log(Some(competitor), MatchAction::Penalty);
+
+        match self.rules.penalty_consequence(penalties) {
+            PenaltyConsequence::OpponentAdvantage => self.score_mut(competitor.opponent()).advantages += 1,
+            PenaltyConsequence::OpponentPoints(points) => self.score_mut(competitor.opponent()).record_points(None, points as isize),
+            PenaltyConsequence::Disqualification => self.finish_match(competitor.opponent(), FinishMethod::Disqualification),
+            PenaltyConsequence::None => {}
+        }
+    }
+
+    /// Records an infraction called against a competitor, with the consequence the rule set gives
+    /// it: a warning, a penalty (escalating as `add_penalty` does), a penalty plus points for the
+    /// opponent, or disqualification. The log records the infraction before any penalty it gives.
+    pub fn add_infraction(&mut self, competitor: CompetitorNumber, infraction: Infraction) {
+        let _ = self.try_add_infraction(competitor, infraction);
+    }
+
+    pub fn try_add_infraction(&mut self, competitor: CompetitorNumber, infraction: Infraction) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        let consequence = self.infraction_consequence(competitor, infraction);
+        let penalizes = matches!(consequence, InfractionConsequence::Penalty | InfractionConsequence::PenaltyAndOpponentPoints(_));
+        if penalizes && !self.rules.uses_field(ScoreField::Penalties) {
+            return Err(MatchError::FieldNotUsed(ScoreField::Penalties));
+        }
+
+        self.push_undo();
+        self.score_mut(competitor).infractions.push(infraction);
+        self.log(Some(competitor), MatchAction::Infraction { infraction, consequence });
+
+        match consequence {
+            InfractionConsequence::Warning => {}
+            InfractionConsequence::Penalty => self.penalize(competitor, Some(infraction)),
+            InfractionConsequence::PenaltyAndOpponentPoints(points) => {
+                if self.rules.uses_field(ScoreField::Points) {
+                    self.score_mut(competitor.opponent()).record_points(None, points as isize);
+                }
+                self.penalize(competitor, Some(infraction));
+            }
+            InfractionConsequence::Disqualification => self.finish_match(competitor.opponent(), FinishMethod::Disqualification)
+        }
+        self.check_mercy_rule();
+        self.check_golden_score();
+        Ok(())
+    }
+
+    /// The consequence the rule set would give if the infraction were called against the
+    /// competitor now, e.g. so a disqualification can be confirmed before it is recorded.
+    pub fn infraction_consequence(&self, competitor: CompetitorNumber, infraction: Infraction) -> InfractionConsequence {
+        let score = match competitor {
+            CompetitorNumber::One => &self.score.competitor_one_score,
+            CompetitorNumber::Two => &self.score.competitor_two_score
         };
+        self.rules.infraction_consequence(infraction, score.infraction_count(infraction) + 1)
     }
 
     pub fn subtract_point(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_subtract_point(competitor);
+    }
+
+    pub fn try_subtract_point(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        if self.score_mut(competitor).points <= 0 {
+            return Err(MatchError::BelowZero(ScoreField::Points));
+        }
+
+        self.push_undo();
         match competitor {
             CompetitorNumber::One => self.score.competitor_one_score.subtract(ScoreField::Points),
             CompetitorNumber::Two => self.score.competitor_two_score.subtract(ScoreField::Points)
         };
+        self.log(Some(competitor), MatchAction::PointSubtracted);
+        self.check_mercy_rule();
+        Ok(())
+    }
+
+    pub fn subtract_advantage(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_subtract_advantage(competitor);
+    }
+
+    pub fn try_subtract_advantage(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        self.ensure_scoring_allowed()?;
+        if self.score_mut(competitor).advantages == 0 {
+            return Err(MatchError::BelowZero(ScoreField::Advantages));
+        }
+
+        self.push_undo();
+        match competitor {
+            CompetitorNumber::One => self.score.competitor_one_score.subtract(ScoreField::Advantages),
+            CompetitorNumber::Two => self.score.competitor_two_score.subtract(ScoreField::Advantages)
+        };
+        self.log(Some(competitor), MatchAction::AdvantageSubtracted);
+        Ok(())
+    }
+
+    /// Removes a penalty, reversing whatever escalation it caused. Removing the penalty that
+    /// disqualified a competitor reopens the match. A penalty given for an infraction also takes
+    /// the infraction off the record, along with any points it gave the opponent.
+    pub fn subtract_penalty(&mut self, competitor: CompetitorNumber) {
+        let _ = self.try_subtract_penalty(competitor);
+    }
+
+    pub fn try_subtract_penalty(&mut self, competitor: CompetitorNumber) -> Result<(), MatchError> {
+        let penalties = self.score_mut(competitor).penalties;
+        if penalties == 0 {
+            return Err(MatchError::BelowZero(ScoreField::Penalties));
+        }
+
+        let consequence = self.rules.penalty_consequence(penalties);
+        let disqualified = MatchResult {
+            winner: competitor.opponent(),
+            criterion: WinCriterion::Finish(FinishMethod::Disqualification)
+        };
+        let reverses_disqualification = consequence == PenaltyConsequence::Disqualification
+            && self.finish.as_ref() == Some(&disqualified);
+        if !reverses_disqualification {
+            self.ensure_scoring_allowed()?;
+        }
+
+        self.push_undo();
+        let opponent = self.score_mut(competitor.opponent());
+
+        match consequence {
+            PenaltyConsequence::OpponentAdvantage => opponent.subtract(ScoreField::Advantages),
+            PenaltyConsequence::OpponentPoints(points) => opponent.remove_points(points),
+            PenaltyConsequence::Disqualification if reverses_disqualification => self.finish = None,
+            PenaltyConsequence::Disqualification | PenaltyConsequence::None => {}
+        }
+
+        if let Some(infraction) = self.score_mut(competitor).pop_penalty_cause() {
+            let count = self.score_mut(competitor).infraction_count(infraction);
+            let infraction_points = match self.rules.infraction_consequence(infraction, count) {
+                InfractionConsequence::PenaltyAndOpponentPoints(points) if self.rules.uses_field(ScoreField::Points) => points,
+                _ => 0
+            };
+            self.score_mut(competitor.opponent()).remove_points(infraction_points);
+        }
+
+        self.score_mut(competitor).subtract(ScoreField::Penalties);
+        self.log(Some(competitor), MatchAction::PenaltySubtracted);
+        self.check_mercy_rule();
+        Ok(())
+    }
+
+    fn score_mut(&mut self, competitor: CompetitorNumber) -> &mut PlayerScore {
+        match competitor {
+            CompetitorNumber::One => &mut self.score.competitor_one_score,
+            CompetitorNumber::Two => &mut self.score.competitor_two_score
+        }
+    }
+
+    pub fn start(&mut self) {
+        let _ = self.try_start();
+    }
+
+    pub fn try_start(&mut self) -> Result<(), MatchError> {
+        self.ensure_state(MatchState::NotStarted)?;
+
+        self.push_undo();
+        self.period = MatchPeriod::Regulation;
+        self.time.set_duration(self.info.match_time_minutes * 60 * 1000);
+        self.time.start();
+        self.log(None, MatchAction::MatchStarted);
+        Ok(())
+    }
+
+    /// Starts or pauses the active clock. A finished match or one awaiting a decision can't be
+    /// restarted.
+    /// Starts or stops the active clock. On a match that hasn't started yet, this starts it.
+    pub fn toggle_start_stop(&mut self) {
+        let _ = self.try_toggle_start_stop();
+    }
+
+    pub fn try_toggle_start_stop(&mut self) -> Result<(), MatchError> {
+        if self.get_match_state() == MatchState::NotStarted {
+            return self.try_start();
+        }
+
+        let next = match (self.clock().is_running(), self.period) {
+            (true, _) => MatchState::Paused,
+            (false, MatchPeriod::Regulation) => MatchState::Running,
+            (false, MatchPeriod::Overtime(_)) => MatchState::Overtime
+        };
+        self.ensure_can_enter(&next)?;
+
+        self.push_undo();
+        let time = self.clock_mut();
+        time.toggle_start_stop();
+
+        match time.is_running() {
+            true => {
+                self.log(None, MatchAction::ClockStarted);
+                self.stop_timeout();
+            }
+            false => self.log(None, MatchAction::ClockStopped)
+        }
+        Ok(())
+    }
+
+    pub fn timeouts(&self) -> &MatchTimeouts {
+        &self.timeouts
+    }
+
+    pub fn set_timeout_limits(&mut self, limits: TimeoutLimits) {
+        self.timeouts.limits = limits;
+    }
+
+    /// Pauses the match clock and starts the competitor's time-out clock of the given kind.
+    pub fn start_timeout(&mut self, competitor: CompetitorNumber, kind: TimeoutKind) {
+        let _ = self.try_start_timeout(competitor, kind);
+    }
+
+    pub fn try_start_timeout(&mut self, competitor: CompetitorNumber, kind: TimeoutKind) -> Result<(), MatchError> {
+        self.ensure_can_enter(&MatchState::InjuryTimeout)?;
+        if self.timeouts.active() == Some((competitor, kind)) {
+            return Err(MatchError::TimeoutAlreadyActive);
+        }
+
+        self.push_undo();
+        self.clock_mut().stop();
+        self.stop_timeout();
+        self.timeouts.start(competitor, kind);
+        self.log(Some(competitor), MatchAction::TimeoutStarted(kind));
+        Ok(())
+    }
+
+    /// Stops the running time-out, ending the match by medical stoppage if the competitor has
+    /// gone over their allowance.
+    pub fn end_timeout(&mut self) {
+        let _ = self.try_end_timeout();
+    }
+
+    pub fn try_end_timeout(&mut self) -> Result<(), MatchError> {
+        if self.timeouts.active().is_none() {
+            return Err(MatchError::NoActiveTimeout);
+        }
+
+        self.push_undo();
+        self.stop_timeout();
+        Ok(())
+    }
+
+    fn stop_timeout(&mut self) {
+        let Some((competitor, kind)) = self.timeouts.active() else {
+            return;
+        };
+
+        self.timeouts.stop();
+        self.log(Some(competitor), MatchAction::TimeoutEnded(kind));
+
+        if self.finish.is_none() && self.timeouts.exceeded() == Some(competitor) {
+            self.finish_match(competitor.opponent(), FinishMethod::Injury);
+        }
+    }
+
+    /// Reverts the most recent scoring or clock action.
+    pub fn undo(&mut self) {
+        let _ = self.try_undo();
+    }
+
+    pub fn try_undo(&mut self) -> Result<(), MatchError> {
+        let snapshot = self.undo_stack.pop().ok_or(MatchError::NothingToUndo)?;
+
+        let current = self.snapshot();
+        self.redo_stack.push(current);
+        self.restore(snapshot);
+        self.log(None, MatchAction::Undo);
+        Ok(())
+    }
+
+    /// Re-applies the most recently undone action.
+    pub fn redo(&mut self) {
+        let _ = self.try_redo();
+    }
+
+    pub fn try_redo(&mut self) -> Result<(), MatchError> {
+        let snapshot = self.redo_stack.pop().ok_or(MatchError::NothingToRedo)?;
+
+        let current = self.snapshot();
+        self.undo_stack.push(current);
+        self.restore(snapshot);
+        self.log(None, MatchAction::Redo);
+        Ok(())
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Saves the current state so the action about to be applied can be undone.
+    fn push_undo(&mut self) {
+        let snapshot = self.snapshot();
+        self.undo_stack.push(snapshot);
+        self.redo_stack.clear();
+    }
+
+    fn snapshot(&self) -> MatchSnapshot {
+        let mut snapshot = MatchSnapshot {
+            score: self.score.clone(),
+            time: self.time.clone(),
+            referee_decision: self.referee_decision,
+            finish: self.finish.clone(),
+            period: self.period,
+            ebi: self.ebi.clone(),
+            timeouts: self.timeouts.clone()
+        };
+        snapshot.time.rebase();
+        if let Some(ebi) = &mut snapshot.ebi {
+            ebi.time.rebase();
+        }
+        snapshot.timeouts.clocks_mut().for_each(MatchTime::rebase);
+        snapshot
+    }
+
+    /// Puts the match back as it was in `snapshot`. A clock that was running then but has been
+    /// stopped since picks up where it was, so undoing a pause doesn't count the paused time.
+    fn restore(&mut self, mut snapshot: MatchSnapshot) {
+        snapshot.time.resume_over(&self.time);
+        if let (Some(restored), Some(current)) = (&mut snapshot.ebi, &self.ebi) {
+            restored.time.resume_over(&current.time);
+        }
+        for (restored, current) in snapshot.timeouts.clocks_mut().zip(self.timeouts.clocks_mut()) {
+            restored.resume_over(current);
+        }
+
+        self.score = snapshot.score;
+        self.time = snapshot.time;
+        self.referee_decision = snapshot.referee_decision;
+        self.finish = snapshot.finish;
+        self.period = snapshot.period;
+        self.ebi = snapshot.ebi;
+        self.timeouts = snapshot.timeouts;
+    }
+
+    /// Puts time back on the active clock, e.g. for seconds that ran during a stoppage. The clock
+    /// of a finished match can't be changed, since that would reopen it.
+    pub fn add_time(&mut self, millis: usize) {
+        let _ = self.try_add_time(millis);
+    }
+
+    pub fn try_add_time(&mut self, millis: usize) -> Result<(), MatchError> {
+        self.ensure_clock_adjustable()?;
+
+        self.push_undo();
+        self.clock_mut().add_time(millis);
+        self.log(None, MatchAction::TimeAdded(millis));
+        Ok(())
+    }
+
+    pub fn subtract_time(&mut self, millis: usize) {
+        let _ = self.try_subtract_time(millis);
+    }
+
+    pub fn try_subtract_time(&mut self, millis: usize) -> Result<(), MatchError> {
+        self.ensure_clock_adjustable()?;
+
+        self.push_undo();
+        self.clock_mut().subtract_time(millis);
+        self.log(None, MatchAction::TimeSubtracted(millis));
+        Ok(())
+    }
+
+    pub fn set_remaining_time(&mut self, millis: usize) {
+        let _ = self.try_set_remaining_time(millis);
+    }
+
+    pub fn try_set_remaining_time(&mut self, millis: usize) -> Result<(), MatchError> {
+        self.ensure_clock_adjustable()?;
+
+        self.push_undo();
+        self.clock_mut().set_remaining_time(millis);
+        self.log(None, MatchAction::RemainingTimeSet(millis));
+        Ok(())
+    }
+
+    pub fn reset_clock(&mut self) {
+        let _ = self.try_reset_clock();
+    }
+
+    pub fn try_reset_clock(&mut self) -> Result<(), MatchError> {
+        self.ensure_clock_adjustable()?;
+
+        self.push_undo();
+        self.clock_mut().reset();
+        self.log(None, MatchAction::ClockReset);
+        Ok(())
+    }
+
+    fn ensure_clock_adjustable(&self) -> Result<(), MatchError> {
+        match self.get_match_state() {
+            state @ MatchState::Finished(_) => Err(MatchError::InvalidState(state)),
+            _ => Ok(())
+        }
+    }
+
+    fn clock_mut(&mut self) -> &mut MatchTime {
+        match &mut self.ebi {
+            Some(ebi) => &mut ebi.time,
+            None => &mut self.time
+        }
+    }
+
+    /// Registers a callback for clock events. Events are only detected when `update` is called.
+    pub fn add_timer_observer(&mut self, observer: TimerObserver) {
+        self.timer_watch.add_observer(observer);
+    }
+
+    /// Sets the remaining times at which a `TimerEvent::Warning` fires. Defaults to 30 and 10
+    /// seconds.
+    pub fn set_warning_thresholds(&mut self, thresholds_millis: Vec<usize>) {
+        self.timer_watch.warning_thresholds_millis = thresholds_millis;
+    }
+
+    /// Checks the active clock for events since the last update, notifying timer observers.
+    /// Front ends should call this every frame.
+    pub fn update(&mut self) -> Vec<TimerEvent> {
+        let time = match &self.ebi {
+            Some(ebi) => &ebi.time,
+            None => &self.time
+        };
+        self.timer_watch.update(time)
+    }
+
+    /// Serializes the match to JSON, including the state of its clocks. Undo history and timer
+    /// observers aren't saved, and only built-in rule sets can be restored.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Restores a match saved with `to_json`. Clocks that were running carry on from where they
+    /// would have got to had they kept running, driven by the system's monotonic clock.
+    pub fn from_json(json: &str) -> serde_json::Result<BJJMatch> {
+        serde_json::from_str(json)
+    }
+
+    /// Like `from_json`, but with every timer driven by `clock` rather than the system's, since
+    /// the clock a match was created with isn't saved.
+    pub fn from_json_with_clock(json: &str, clock: Arc<dyn Clock>) -> serde_json::Result<BJJMatch> {
+        let mut bjj_match = BJJMatch::from_json(json)?;
+        bjj_match.time.set_clock(Arc::clone(&clock));
+        for time in bjj_match.timeouts.clocks_mut() {
+            time.set_clock(Arc::clone(&clock));
+        }
+        if let Some(ebi) = &mut bjj_match.ebi {
+            ebi.time.set_clock(clock);
+        }
+        Ok(bjj_match)
+    }
+
+    /// Writes the match to a temporary file next to `path` and renames it into place, so a crash
+    /// mid-save never leaves a half-written file behind.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+        fs::write(&temp_path, self.to_json()?)?;
+        fs::rename(temp_path, path)
     }
 
-    pub fn subtract_advantage(&mut self, competitor: CompetitorNumber) {
-        match competitor {
-            CompetitorNumber::One => self.score.competitor_one_score.subtract(ScoreField::Advantages),
-            CompetitorNumber::Two => self.score.competitor_two_score.subtract(ScoreField::Advantages)
-        };
+    pub fn load(path: impl AsRef<Path>) -> io::Result<BJJMatch> {
+        Ok(BJJMatch::from_json(&fs::read_to_string(path)?)?)
     }
 
-    pub fn subtract_penalty(&mut self, competitor: CompetitorNumber) {
-        match competitor {
-            CompetitorNumber::One => self.score.competitor_one_score.subtract(ScoreField::Penalties),
-            CompetitorNumber::Two => self.score.competitor_two_score.subtract(ScoreField::Penalties)
-        };
+    /// Restores a match saved with `save` after a crash. Unlike `load`, every clock is stopped at
+    /// the time it showed when the match was saved, since the match was interrupted, and a
+    /// time-out in progress is paused so the operator can resume it.
+    pub fn recover(path: impl AsRef<Path>) -> io::Result<BJJMatch> {
+        let mut bjj_match = BJJMatch::load(path)?;
+        bjj_match.time.stop_at_saved();
+        bjj_match.timeouts.stop_at_saved();
+        if let Some(ebi) = &mut bjj_match.ebi {
+            ebi.time.stop_at_saved();
+        }
+        Ok(bjj_match)
     }
 
-    pub fn start(&mut self) {
-        self.time.duration_millis = self.info.match_time_minutes * 60 * 1000;
-        self.time.start();
+    /// Every action taken during the match, oldest first.
+    pub fn events(&self) -> &[MatchEvent] {
+        &self.events
     }
 
-    pub fn toggle_start_stop(&mut self) {
-        self.time.toggle_start_stop();
+    fn log(&mut self, competitor: Option<CompetitorNumber>, action: MatchAction) {
+        self.events.push(MatchEvent {
+            clock_millis: self.clock().get_elapsed_time_milliseconds(),
+            period: self.period,
+            wall_time: SystemTime::now(),
+            competitor,
+            action
+        });
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchInformation {
     pub competitor_one: Competitor,
     pub competitor_two: Competitor,
@@ -177,12 +1403,19 @@ impl Default for MatchInformation {
     }
 }
 
-#[derive(Debug)]
+/// Fields missing from a saved competitor take their default values.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Competitor {
     pub first_name: String,
     pub last_name: String,
     pub team_name: String,
-    pub country: Country
+    pub country: Country,
+    pub belt: Belt,
+    pub weight_class: WeightClass,
+    pub age_division: AgeDivision,
+    pub uniform: Uniform,
+    pub athlete_id: Option<AthleteId>
 }
 
 impl Default for Competitor {
@@ -191,7 +1424,12 @@ impl Default for Competitor {
             first_name: "Competitor".to_owned(),
             last_name: "Name".to_owned(),
             team_name: "BJJ Team".to_owned(),
-            country: Country::Australia
+            country: Country::Australia,
+            belt: Belt::White,
+            weight_class: WeightClass::Open,
+            age_division: AgeDivision::Adult,
+            uniform: Uniform::Gi,
+            athlete_id: None
         }
     }
 }
@@ -202,30 +1440,122 @@ impl Competitor {
             first_name: first_name.to_owned(),
             last_name: last_name.to_owned(),
             team_name: team_name.to_owned(),
-            country
+            country,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_division(self, belt: Belt, age_division: AgeDivision, weight_class: WeightClass, uniform: Uniform) -> Competitor {
+        Competitor {
+            belt,
+            age_division,
+            weight_class,
+            uniform,
+            ..self
         }
     }
+
+    pub fn with_athlete_id(self, athlete_id: &str) -> Competitor {
+        Competitor {
+            athlete_id: Some(AthleteId(athlete_id.to_owned())),
+            ..self
+        }
+    }
+
     pub fn get_display_name(&self) -> String {
         format!("{} {}", self.first_name, self.last_name)
     }
+
+    /// The division the competitor is entered in, e.g. "Black Belt Adult Light Gi".
+    pub fn get_division_name(&self) -> String {
+        format!("{} Belt {} {} {}", self.belt, self.age_division, self.weight_class, self.uniform)
+    }
 }
 
 
-#[derive(Default, Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "SavedMatchTime", from = "SavedMatchTime")]
 pub struct MatchTime {
     duration_millis: usize,
-    last_started: Option<SystemTime>,
+    /// The duration the clock was created with, before `set_remaining_time` extended it.
+    original_duration_millis: usize,
+    last_started: Option<Instant>,
     time_elapsed_millis: usize,
-    running: bool
+    running: bool,
+    counts_up: bool,
+    clock: Arc<dyn Clock>,
+    /// The time elapsed when the clock was saved, if it was loaded from a save.
+    saved_elapsed_millis: Option<usize>
+}
+
+impl Default for MatchTime {
+    fn default() -> Self {
+        Self {
+            duration_millis: 0,
+            original_duration_millis: 0,
+            last_started: None,
+            time_elapsed_millis: 0,
+            running: false,
+            counts_up: false,
+            clock: Arc::new(MonotonicClock),
+            saved_elapsed_millis: None
+        }
+    }
 }
 
 impl MatchTime {
+    pub fn new(duration_millis: usize) -> MatchTime {
+        MatchTime {
+            duration_millis,
+            original_duration_millis: duration_millis,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_clock(duration_millis: usize, clock: Arc<dyn Clock>) -> MatchTime {
+        MatchTime {
+            duration_millis,
+            original_duration_millis: duration_millis,
+            clock,
+            ..Default::default()
+        }
+    }
+
+    /// A new, stopped timer of the given duration driven by the same clock.
+    pub fn fresh(&self, duration_millis: usize) -> MatchTime {
+        MatchTime::with_clock(duration_millis, Arc::clone(&self.clock))
+    }
+
+    /// A new, stopped timer with no time limit that shows the time elapsed rather than remaining.
+    pub fn fresh_count_up(&self) -> MatchTime {
+        MatchTime {
+            counts_up: true,
+            ..self.fresh(usize::MAX)
+        }
+    }
+
+    pub fn counts_up(&self) -> bool {
+        self.counts_up
+    }
+
+    /// The time to show on the scoreboard: remaining time, or elapsed time for count-up timers.
+    pub fn get_display_time_milliseconds(&self) -> usize {
+        match self.counts_up {
+            true => self.get_elapsed_time_milliseconds(),
+            false => self.get_remaining_time_milliseconds()
+        }
+    }
+
     pub fn get_remaining_time_milliseconds(&self) -> usize {
-        let elapsed = match &self.last_started {
+        self.duration_millis.saturating_sub(self.get_elapsed_time_milliseconds())
+    }
+
+    pub fn get_elapsed_time_milliseconds(&self) -> usize {
+        match &self.last_started {
             Some(start_time) => {
                 match self.running {
                     true => {
-                        self.time_elapsed_millis + SystemTime::now().duration_since(*start_time).unwrap_or(Duration::new(0, 0)).as_millis() as usize
+                        self.time_elapsed_millis + self.clock.now().saturating_duration_since(*start_time).as_millis() as usize
                     },
                     false => {
                         self.time_elapsed_millis
@@ -235,9 +1565,93 @@ impl MatchTime {
             None => {
                 self.time_elapsed_millis
             }
-        };
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn get_duration_milliseconds(&self) -> usize {
+        self.duration_millis
+    }
+
+    /// Puts time back on the clock, up to the full duration.
+    pub fn add_time(&mut self, millis: usize) {
+        let elapsed = self.get_elapsed_time_milliseconds();
+        self.set_elapsed(elapsed.saturating_sub(millis));
+    }
+
+    /// Takes time off the clock, down to zero remaining.
+    pub fn subtract_time(&mut self, millis: usize) {
+        let elapsed = self.get_elapsed_time_milliseconds();
+        self.set_elapsed((elapsed + millis).min(self.duration_millis));
+    }
+
+    /// Sets the remaining time exactly, extending the duration if needed. Count-up timers set the
+    /// elapsed time instead.
+    pub fn set_remaining_time(&mut self, millis: usize) {
+        if self.counts_up {
+            self.set_elapsed(millis);
+            return;
+        }
+
+        self.duration_millis = self.duration_millis.max(millis);
+        self.set_elapsed(self.duration_millis - millis);
+    }
 
-        self.duration_millis.saturating_sub(elapsed)
+    /// Stops the clock and puts it back to its original full duration, undoing any extension by
+    /// `set_remaining_time`, as if it had never been started.
+    pub fn reset(&mut self) {
+        self.duration_millis = self.original_duration_millis;
+        self.running = false;
+        self.last_started = None;
+        self.time_elapsed_millis = 0;
+    }
+
+    /// Drives the timer from `clock` from now on, keeping the time it shows.
+    pub(crate) fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let running = self.running;
+        self.stop();
+        self.clock = clock;
+        if running {
+            self.start();
+        }
+    }
+
+    /// Stops a clock loaded from a save at the time it showed when it was saved, dropping the time
+    /// that passed while the match wasn't running.
+    pub(crate) fn stop_at_saved(&mut self) {
+        self.stop();
+        if let Some(elapsed) = self.saved_elapsed_millis.take() {
+            self.time_elapsed_millis = elapsed;
+        }
+    }
+
+    /// Moves a running clock's start up to now without changing the time it shows, so a copy
+    /// taken now remembers this reading.
+    fn rebase(&mut self) {
+        self.set_elapsed(self.get_elapsed_time_milliseconds());
+    }
+
+    /// Restarts a copy taken with `rebase` from the reading it had then, if it was running and
+    /// `current`, the clock it is replacing, has been stopped since.
+    fn resume_over(&mut self, current: &MatchTime) {
+        if self.running && !current.running {
+            self.last_started = Some(self.clock.now());
+        }
+    }
+
+    fn set_duration(&mut self, duration_millis: usize) {
+        self.duration_millis = duration_millis;
+        self.original_duration_millis = duration_millis;
+    }
+
+    fn set_elapsed(&mut self, millis: usize) {
+        if self.running {
+            self.last_started = Some(self.clock.now());
+        }
+        self.time_elapsed_millis = millis;
     }
 
     pub fn toggle_start_stop(&mut self) {
@@ -254,7 +1668,7 @@ impl MatchTime {
         }
 
         self.running = true;
-        self.last_started = Some(SystemTime::now());
+        self.last_started = Some(self.clock.now());
     }
 
     pub fn stop(&mut self) {
@@ -264,7 +1678,7 @@ impl MatchTime {
 
         let elapsed = match &self.last_started {
             Some(start_time) => {
-                SystemTime::now().duration_since(start_time.clone()).unwrap_or(Duration::new(0,0)).as_millis() as usize
+                self.clock.now().saturating_duration_since(*start_time).as_millis() as usize
             },
             None => 0
         };
@@ -272,4 +1686,800 @@ impl MatchTime {
         self.running = false;
         self.time_elapsed_millis += elapsed;
     }
-}
\ No newline at end of file
+}
+
+/// A `MatchTime` as saved: the time elapsed at the moment it was saved and, so a running clock
+/// can carry on, the wall time it was saved at.
+#[derive(Serialize, Deserialize)]
+struct SavedMatchTime {
+    duration_millis: usize,
+    #[serde(default)]
+    original_duration_millis: Option<usize>,
+    elapsed_millis: usize,
+    started: bool,
+    running: bool,
+    counts_up: bool,
+    saved_at: SystemTime
+}
+
+impl From<MatchTime> for SavedMatchTime {
+    fn from(time: MatchTime) -> Self {
+        SavedMatchTime {
+            duration_millis: time.duration_millis,
+            original_duration_millis: Some(time.original_duration_millis),
+            elapsed_millis: time.get_elapsed_time_milliseconds(),
+            started: time.last_started.is_some(),
+            running: time.running,
+            counts_up: time.counts_up,
+            saved_at: SystemTime::now()
+        }
+    }
+}
+
+impl From<SavedMatchTime> for MatchTime {
+    fn from(saved: SavedMatchTime) -> Self {
+        let clock: Arc<dyn Clock> = Arc::new(MonotonicClock);
+        let since_saved = match saved.running {
+            true => SystemTime::now().duration_since(saved.saved_at).unwrap_or_default().as_millis() as usize,
+            false => 0
+        };
+
+        MatchTime {
+            duration_millis: saved.duration_millis,
+            original_duration_millis: saved.original_duration_millis.unwrap_or(saved.duration_millis),
+            last_started: saved.started.then(|| clock.now()),
+            time_elapsed_millis: saved.elapsed_millis + since_saved,
+            running: saved.running,
+            counts_up: saved.counts_up,
+            clock,
+            saved_elapsed_millis: Some(saved.elapsed_millis)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BJJMatch, Competitor, CompetitorNumber, FinishMethod, MatchConfigError, MatchError, MatchPeriod, MatchResult, MatchState, ScoreField, WinCriterion};
+    use crate::rules::{self, Adcc, Ebi, GoldenScore, Ibjjf, Overtime, PenaltyConsequence, RuleSet, SubmissionOnly};
+    use crate::{AgeDivision, AthleteId, Belt, DurationTable, Infraction, InfractionConsequence, Uniform, WeightClass};
+    use crate::{Country, EbiOutcome, EbiStartPosition, MatchAction, MockClock, ScoringAction, TechniqueBreakdown, TimeoutKind, TimerEvent};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    fn finished_match() -> BJJMatch {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 0, 1, 1);
+        bjj_match.start();
+        bjj_match
+    }
+
+    fn timed_match() -> (BJJMatch, MockClock) {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        (bjj_match, clock)
+    }
+
+    #[test]
+    fn test_result_tie_break_order() {
+        let (mut bjj_match, clock) = timed_match();
+        bjj_match.add_penalty(CompetitorNumber::One);
+        assert_eq!(bjj_match.result(), None);
+
+        clock.advance_millis(300_000);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::Penalties }));
+
+        let (mut bjj_match, clock) = timed_match();
+        bjj_match.add_penalty(CompetitorNumber::One);
+        bjj_match.add_advantage(CompetitorNumber::One);
+        clock.advance_millis(300_000);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::One, criterion: WinCriterion::Advantages }));
+
+        let (mut bjj_match, clock) = timed_match();
+        bjj_match.add_penalty(CompetitorNumber::One);
+        bjj_match.add_advantage(CompetitorNumber::One);
+        bjj_match.add_points(2, CompetitorNumber::Two);
+        clock.advance_millis(300_000);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::Points }));
+
+        // Changing the clock would reopen the finished match.
+        let finished = MatchState::Finished(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::Points });
+        assert_eq!(bjj_match.try_add_time(5_000).err(), Some(MatchError::InvalidState(finished.clone())));
+        assert_eq!(bjj_match.try_reset_clock().err(), Some(MatchError::InvalidState(finished.clone())));
+        assert_eq!(bjj_match.try_set_remaining_time(60_000).err(), Some(MatchError::InvalidState(finished.clone())));
+        assert_eq!(bjj_match.get_match_state(), finished);
+    }
+
+    #[test]
+    fn test_match_state_transitions() {
+        let (mut bjj_match, clock) = timed_match();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Running);
+
+        bjj_match.toggle_start_stop();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Paused);
+
+        bjj_match.start_timeout(CompetitorNumber::Two, TimeoutKind::Blood);
+        assert_eq!(bjj_match.get_match_state(), MatchState::InjuryTimeout);
+        bjj_match.add_penalty(CompetitorNumber::Two);
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 1);
+
+        bjj_match.toggle_start_stop();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Running);
+
+        bjj_match.add_penalty(CompetitorNumber::One);
+        clock.advance_millis(300_000);
+        assert_eq!(bjj_match.get_match_state(), MatchState::AwaitingDecision);
+
+        bjj_match.toggle_start_stop();
+        bjj_match.add_points(2, CompetitorNumber::One);
+        assert_eq!(bjj_match.get_match_state(), MatchState::AwaitingDecision);
+        assert_eq!(bjj_match.score.competitor_one_score.points, 0);
+
+        bjj_match.set_referee_decision(CompetitorNumber::One);
+        let result = MatchResult { winner: CompetitorNumber::One, criterion: WinCriterion::RefereeDecision };
+        assert_eq!(bjj_match.get_match_state(), MatchState::Finished(result.clone()));
+
+        bjj_match.submission(CompetitorNumber::Two, "Armbar");
+        bjj_match.start();
+        assert_eq!(bjj_match.result(), Some(result));
+    }
+
+    #[test]
+    fn test_result_referee_decision() {
+        let mut bjj_match = finished_match();
+        bjj_match.set_referee_decision(CompetitorNumber::Two);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::RefereeDecision }));
+    }
+
+    #[test]
+    fn test_match_errors() {
+        let (mut bjj_match, _clock) = timed_match();
+        assert_eq!(bjj_match.try_start(), Err(MatchError::InvalidState(MatchState::Running)));
+        assert_eq!(bjj_match.try_subtract_advantage(CompetitorNumber::One), Err(MatchError::BelowZero(ScoreField::Advantages)));
+        assert_eq!(bjj_match.try_end_timeout(), Err(MatchError::NoActiveTimeout));
+        assert_eq!(bjj_match.try_add_points(2, CompetitorNumber::One), Ok(()));
+
+        bjj_match.submission(CompetitorNumber::One, "Kimura");
+        let state = bjj_match.get_match_state();
+        assert_eq!(bjj_match.try_add_advantage(CompetitorNumber::Two), Err(MatchError::InvalidState(state.clone())));
+        assert_eq!(bjj_match.try_toggle_start_stop(), Err(MatchError::InvalidState(state)));
+        assert_eq!(bjj_match.try_redo(), Err(MatchError::NothingToRedo));
+    }
+
+    #[test]
+    fn test_finish_by_submission() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.start();
+        bjj_match.add_points(4, CompetitorNumber::One);
+        bjj_match.submission(CompetitorNumber::Two, "Armbar");
+
+        assert_eq!(bjj_match.get_match_state(), MatchState::Finished(MatchResult {
+            winner: CompetitorNumber::Two,
+            criterion: WinCriterion::Finish(FinishMethod::Submission("Armbar".to_owned()))
+        }));
+
+        bjj_match.add_points(2, CompetitorNumber::One);
+        assert_eq!(bjj_match.score.competitor_one_score.points, 4);
+    }
+
+    #[test]
+    fn test_penalty_escalation() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.start();
+
+        for _ in 0..3 {
+            bjj_match.add_penalty(CompetitorNumber::One);
+        }
+        assert_eq!(bjj_match.score.competitor_two_score.advantages, 1);
+        assert_eq!(bjj_match.score.competitor_two_score.points, 2);
+
+        bjj_match.add_penalty(CompetitorNumber::One);
+        assert_eq!(bjj_match.result().map(|r| r.criterion), Some(WinCriterion::Finish(FinishMethod::Disqualification)));
+
+        for _ in 0..4 {
+            bjj_match.subtract_penalty(CompetitorNumber::One);
+        }
+        assert_eq!(bjj_match.get_match_state(), MatchState::Paused);
+        assert_eq!(bjj_match.score.competitor_two_score.advantages, 0);
+        assert_eq!(bjj_match.score.competitor_two_score.points, 0);
+    }
+
+    #[test]
+    fn test_submission_only_ignores_points() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 0, 1, 1)
+            .with_rules(Box::new(SubmissionOnly));
+        bjj_match.start();
+        bjj_match.add_points(2, CompetitorNumber::One);
+        bjj_match.add_advantage(CompetitorNumber::One);
+
+        assert_eq!(bjj_match.score.competitor_one_score.points, 0);
+        assert_eq!(bjj_match.score.competitor_one_score.advantages, 0);
+        assert_eq!(bjj_match.result(), None);
+    }
+
+    #[test]
+    fn test_adcc_no_points_period_and_negative_points() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 10, 1, 1)
+            .with_rules(Box::new(Adcc))
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        bjj_match.add_points(2, CompetitorNumber::One);
+        bjj_match.add_negative_point(CompetitorNumber::Two);
+
+        assert_eq!(bjj_match.score.competitor_one_score.points, 0);
+        assert_eq!(bjj_match.score.competitor_two_score.points, -1);
+
+        bjj_match.add_negative_point(CompetitorNumber::One);
+        bjj_match.start_overtime();
+        assert_eq!(bjj_match.period(), MatchPeriod::Regulation);
+
+        clock.advance_millis(600_000);
+        assert_eq!(bjj_match.get_match_state(), MatchState::AwaitingDecision);
+        bjj_match.start_overtime();
+        bjj_match.add_points(2, CompetitorNumber::One);
+        assert_eq!(bjj_match.period(), MatchPeriod::Overtime(1));
+        assert_eq!(bjj_match.score.competitor_one_score.points, 1);
+    }
+
+    #[test]
+    fn test_ebi_overtime_submission_beats_escape() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 0, 1, 1)
+            .with_rules(Box::new(Ebi));
+        bjj_match.start();
+        assert_eq!(bjj_match.result(), None);
+
+        bjj_match.start_overtime();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Overtime);
+
+        bjj_match.start_ebi_turn(EbiStartPosition::BackControl);
+        bjj_match.end_ebi_turn(EbiOutcome::Escape);
+        bjj_match.start_ebi_turn(EbiStartPosition::SpiderWeb);
+        bjj_match.end_ebi_turn(EbiOutcome::Submission);
+
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::OvertimeSubmission }));
+    }
+
+    #[test]
+    fn test_ebi_overtime_escape_time() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 0, 1, 1)
+            .with_rules(Box::new(Ebi));
+        bjj_match.start();
+        bjj_match.start_overtime();
+
+        for _ in 0..3 {
+            bjj_match.start_ebi_turn(EbiStartPosition::BackControl);
+            bjj_match.end_ebi_turn(EbiOutcome::TimeExpired);
+            bjj_match.start_ebi_turn(EbiStartPosition::BackControl);
+            bjj_match.end_ebi_turn(EbiOutcome::Escape);
+        }
+
+        let ebi = bjj_match.ebi_overtime().unwrap();
+        assert_eq!(ebi.round(), 4);
+        assert_eq!(ebi.cumulative_escape_millis(CompetitorNumber::Two), 3 * 4 * 60 * 1000);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::One, criterion: WinCriterion::OvertimeEscape }));
+    }
+
+    #[test]
+    fn test_technique_breakdown() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.start();
+        bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::One);
+        bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::One);
+        bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::One);
+
+        assert_eq!(bjj_match.score.competitor_one_score.points, 8);
+        assert_eq!(bjj_match.score.competitor_one_score.technique_breakdown(), vec![
+            TechniqueBreakdown { action: Some(ScoringAction::Takedown), count: 2, points: 4 },
+            TechniqueBreakdown { action: Some(ScoringAction::Mount), count: 1, points: 4 }
+        ]);
+    }
+
+    #[test]
+    fn test_technique_breakdown_adds_up_after_corrections() {
+        let (mut bjj_match, _clock) = timed_match();
+        let breakdown_total = |bjj_match: &BJJMatch| bjj_match.score.competitor_one_score.technique_breakdown().iter().map(|row| row.points).sum::<isize>();
+
+        bjj_match.add_points(3, CompetitorNumber::One);
+        bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::One);
+        bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::One);
+        bjj_match.subtract_point(CompetitorNumber::One);
+        assert_eq!(bjj_match.score.competitor_one_score.points, 7);
+        assert_eq!(breakdown_total(&bjj_match), 7);
+        assert_eq!(bjj_match.score.competitor_one_score.technique_breakdown(), vec![
+            TechniqueBreakdown { action: Some(ScoringAction::Sweep), count: 1, points: 1 },
+            TechniqueBreakdown { action: Some(ScoringAction::GuardPass), count: 1, points: 3 },
+            TechniqueBreakdown { action: None, count: 1, points: 3 }
+        ]);
+
+        bjj_match.subtract_point(CompetitorNumber::One);
+        assert_eq!(breakdown_total(&bjj_match), 6);
+        assert_eq!(bjj_match.score.competitor_one_score.technique_breakdown().len(), 2);
+
+        bjj_match.undo();
+        bjj_match.undo();
+        assert_eq!(bjj_match.score.competitor_one_score.points, 8);
+        assert_eq!(breakdown_total(&bjj_match), 8);
+    }
+
+    #[test]
+    fn test_event_log() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.start();
+        bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::Two);
+        bjj_match.subtract_advantage(CompetitorNumber::One);
+        bjj_match.toggle_start_stop();
+
+        let actions: Vec<(Option<CompetitorNumber>, MatchAction)> = bjj_match.events()
+            .iter()
+            .map(|event| (event.competitor, event.action.clone()))
+            .collect();
+        assert_eq!(actions, vec![
+            (None, MatchAction::MatchStarted),
+            (Some(CompetitorNumber::Two), MatchAction::Scored { action: ScoringAction::GuardPass, points: 3 }),
+            (None, MatchAction::ClockStopped)
+        ]);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.start();
+        bjj_match.add_points(4, CompetitorNumber::One);
+        for _ in 0..4 {
+            bjj_match.add_penalty(CompetitorNumber::Two);
+        }
+        assert!(matches!(bjj_match.get_match_state(), MatchState::Finished(_)));
+
+        bjj_match.undo();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Running);
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 3);
+
+        bjj_match.redo();
+        assert!(matches!(bjj_match.get_match_state(), MatchState::Finished(_)));
+
+        bjj_match.undo();
+        bjj_match.toggle_start_stop();
+        assert!(!bjj_match.time.is_running());
+        assert!(!bjj_match.can_redo());
+
+        bjj_match.undo();
+        assert!(bjj_match.time.is_running());
+    }
+
+    #[test]
+    fn test_toggle_starts_match() {
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1);
+        bjj_match.toggle_start_stop();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Running);
+        assert_eq!(bjj_match.time.get_duration_milliseconds(), 300_000);
+        assert_eq!(bjj_match.events().last().map(|event| &event.action), Some(&MatchAction::MatchStarted));
+    }
+
+    #[test]
+    fn test_undo_pause() {
+        let (mut bjj_match, clock) = timed_match();
+        clock.advance_millis(60_000);
+        bjj_match.toggle_start_stop();
+        clock.advance_millis(60_000);
+
+        bjj_match.undo();
+        assert!(bjj_match.time.is_running());
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 240_000);
+        clock.advance_millis(10_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 230_000);
+
+        // Undoing a score keeps the time that ran since.
+        bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::One);
+        clock.advance_millis(5_000);
+        bjj_match.undo();
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 225_000);
+    }
+
+    #[test]
+    fn test_match_time_with_mock_clock() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        assert_eq!(bjj_match.get_match_state(), MatchState::NotStarted);
+
+        bjj_match.start();
+        clock.advance_millis(90_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 210_000);
+
+        bjj_match.toggle_start_stop();
+        clock.advance_millis(30_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 210_000);
+        assert_eq!(bjj_match.get_match_state(), MatchState::Paused);
+
+        bjj_match.toggle_start_stop();
+        clock.advance_millis(210_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 0);
+        assert_eq!(bjj_match.get_match_state(), MatchState::AwaitingDecision);
+    }
+
+    #[test]
+    fn test_clock_adjustments() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        clock.advance_millis(60_000);
+
+        bjj_match.add_time(10_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 250_000);
+
+        clock.advance_millis(5_000);
+        bjj_match.subtract_time(15_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 230_000);
+
+        bjj_match.set_remaining_time(400_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 400_000);
+        assert_eq!(bjj_match.time.get_duration_milliseconds(), 400_000);
+
+        bjj_match.reset_clock();
+        assert_eq!(bjj_match.get_match_state(), MatchState::NotStarted);
+        assert_eq!(bjj_match.time.get_duration_milliseconds(), 300_000);
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 300_000);
+        assert_eq!(bjj_match.events().last().map(|event| &event.action), Some(&MatchAction::ClockReset));
+    }
+
+    #[test]
+    fn test_golden_score_first_score_wins() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_rules(Box::new(GoldenScore::new("Golden Score", Ibjjf, None)))
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        clock.advance_millis(300_000);
+        assert_eq!(bjj_match.get_match_state(), MatchState::AwaitingDecision);
+
+        bjj_match.start_overtime();
+        clock.advance_millis(400_000);
+        assert_eq!(bjj_match.get_match_state(), MatchState::Overtime);
+        assert_eq!(bjj_match.time.get_display_time_milliseconds(), 400_000);
+
+        bjj_match.add_advantage(CompetitorNumber::Two);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::GoldenScore }));
+    }
+
+    #[test]
+    fn test_injury_timeout_limit() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        clock.advance_millis(10_000);
+
+        bjj_match.start_timeout(CompetitorNumber::One, TimeoutKind::Injury);
+        assert!(!bjj_match.time.is_running());
+        clock.advance_millis(90_000);
+        bjj_match.toggle_start_stop();
+        assert_eq!(bjj_match.timeouts().active(), None);
+        assert_eq!(bjj_match.timeouts().remaining_millis(CompetitorNumber::One, TimeoutKind::Injury), Some(30_000));
+
+        bjj_match.start_timeout(CompetitorNumber::One, TimeoutKind::Injury);
+        clock.advance_millis(30_000);
+        assert!(matches!(bjj_match.get_match_state(), MatchState::Finished(_)));
+
+        bjj_match.end_timeout();
+        assert_eq!(bjj_match.time.get_remaining_time_milliseconds(), 290_000);
+        assert_eq!(bjj_match.result(), Some(MatchResult {
+            winner: CompetitorNumber::Two,
+            criterion: WinCriterion::Finish(FinishMethod::Injury)
+        }));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_rules(Box::new(Adcc))
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        clock.advance_millis(200_000);
+        bjj_match.add_points(2, CompetitorNumber::Two);
+        bjj_match.toggle_start_stop();
+
+        let restored = BJJMatch::from_json(&bjj_match.to_json().unwrap()).unwrap();
+        assert_eq!(restored.rules().name(), "ADCC");
+        assert_eq!(restored.get_match_state(), MatchState::Paused);
+        assert_eq!(restored.score.competitor_two_score.points, 2);
+        assert_eq!(restored.time.get_remaining_time_milliseconds(), 100_000);
+        assert_eq!(restored.events(), bjj_match.events());
+
+        bjj_match.toggle_start_stop();
+        let restored = BJJMatch::from_json(&bjj_match.to_json().unwrap()).unwrap();
+        assert_eq!(restored.get_match_state(), MatchState::Running);
+        assert!(restored.time.get_remaining_time_milliseconds() <= 100_000);
+    }
+
+    #[derive(Debug)]
+    struct HouseRules;
+
+    impl RuleSet for HouseRules {
+        fn name(&self) -> &str {
+            "House Rules"
+        }
+
+        fn uses_field(&self, _field: ScoreField) -> bool {
+            true
+        }
+
+        fn penalty_consequence(&self, _penalty_count: usize) -> PenaltyConsequence {
+            PenaltyConsequence::None
+        }
+
+        fn tie_breakers(&self) -> Vec<WinCriterion> {
+            vec![WinCriterion::Points]
+        }
+
+        fn overtime(&self) -> Overtime {
+            Overtime::None
+        }
+    }
+
+    #[test]
+    fn test_save_custom_rules() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()))
+            .with_rules(Box::new(GoldenScore::new("Finals", Adcc, Some(90_000))));
+        bjj_match.start();
+        clock.advance_millis(60_000);
+
+        let restored = BJJMatch::from_json_with_clock(&bjj_match.to_json().unwrap(), Arc::new(clock.clone())).unwrap();
+        assert_eq!(restored.rules().name(), "Finals");
+        assert_eq!(restored.rules().config(), bjj_match.rules().config());
+        assert_eq!(restored.rules().overtime(), Overtime::GoldenScore { duration_millis: Some(90_000) });
+        assert!(restored.rules().allows_negative_points());
+        assert_eq!(restored.time.get_remaining_time_milliseconds(), 240_000);
+        clock.advance_millis(10_000);
+        assert_eq!(restored.time.get_remaining_time_milliseconds(), 230_000);
+
+        // Matches saved before rule set configs were saved have just the preset name.
+        let mut json: serde_json::Value = serde_json::from_str(&BJJMatch::default().to_json().unwrap()).unwrap();
+        json["rules"] = "IBJJF Golden Score".into();
+        let restored = BJJMatch::from_json(&json.to_string()).unwrap();
+        assert_eq!(restored.rules().config(), rules::preset("IBJJF Golden Score").and_then(|rules| rules.config()));
+
+        let bjj_match = BJJMatch::default().with_rules(Box::new(HouseRules));
+        assert!(bjj_match.to_json().is_err());
+    }
+
+    #[test]
+    fn test_recover_stops_clocks() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 5, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        bjj_match.start();
+        clock.advance_millis(60_000);
+        bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::One);
+
+        let path = std::env::temp_dir().join(format!("bjj_scoreboard_recover_{}.json", std::process::id()));
+        bjj_match.save(&path).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+        let recovered = BJJMatch::recover(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let recovered = recovered.unwrap();
+        assert_eq!(recovered.get_match_state(), MatchState::Paused);
+        assert_eq!(recovered.time.get_remaining_time_milliseconds(), 240_000);
+        assert_eq!(recovered.score.competitor_one_score.points, 2);
+    }
+
+    #[test]
+    fn test_recover_during_timeout() {
+        let (mut bjj_match, clock) = timed_match();
+        clock.advance_millis(30_000);
+        bjj_match.start_timeout(CompetitorNumber::Two, TimeoutKind::Injury);
+        clock.advance_millis(45_000);
+
+        let path = std::env::temp_dir().join(format!("bjj_scoreboard_recover_timeout_{}.json", std::process::id()));
+        bjj_match.save(&path).unwrap();
+        let recovered = BJJMatch::recover(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let mut recovered = recovered.unwrap();
+        assert_eq!(recovered.get_match_state(), MatchState::Paused);
+        assert_eq!(recovered.timeouts().active(), None);
+        assert_eq!(recovered.time.get_remaining_time_milliseconds(), 270_000);
+        let injury = recovered.timeouts().competitor(CompetitorNumber::Two).get(TimeoutKind::Injury);
+        assert!(!injury.is_running());
+        assert_eq!(injury.get_elapsed_time_milliseconds(), 45_000);
+
+        recovered.start_timeout(CompetitorNumber::Two, TimeoutKind::Injury);
+        assert_eq!(recovered.get_match_state(), MatchState::InjuryTimeout);
+    }
+
+    #[test]
+    fn test_competitor_profile() {
+        let competitor = Competitor::new("Shane", "Poppleton", "Fight Club Jiu-Jitsu", Country::Australia)
+            .with_division(Belt::Brown, AgeDivision::Master1, WeightClass::MediumHeavy, Uniform::NoGi)
+            .with_athlete_id("AU-1234");
+        assert_eq!(competitor.get_division_name(), "Brown Belt Master 1 Medium Heavy No-Gi");
+        assert_eq!(competitor.athlete_id, Some(AthleteId("AU-1234".to_owned())));
+
+        let saved: Competitor = serde_json::from_str(r#"{"first_name": "Ronaldo", "last_name": "Mendes Dos Santos", "team_name": "Caza BJJ", "country": "Brazil"}"#).unwrap();
+        assert_eq!(saved.belt, Belt::White);
+        assert_eq!(saved.athlete_id, None);
+    }
+
+    #[test]
+    fn test_match_builder() {
+        let shane = Competitor::new("Shane", "Poppleton", "Fight Club Jiu-Jitsu", Country::Australia);
+        let ronaldo = Competitor::new("Ronaldo", "Mendes Dos Santos", "Caza BJJ", Country::Brazil);
+        let bjj_match = BJJMatch::builder()
+            .competitor_one(shane.clone())
+            .competitor_two(ronaldo.clone())
+            .match_time_minutes(6)
+            .mat_number(3)
+            .fight_number(12)
+            .rules(Box::new(Adcc))
+            .build()
+            .unwrap();
+        assert_eq!(bjj_match.info.mat_number, 3);
+        assert_eq!(bjj_match.rules().name(), "ADCC");
+
+        let builder = || BJJMatch::builder().competitor_one(shane.clone()).competitor_two(ronaldo.clone());
+        assert_eq!(builder().competitor_two(Competitor::new("Ronaldo", " ", "Caza BJJ", Country::Brazil)).build().err(),
+            Some(MatchConfigError::MissingName(CompetitorNumber::Two)));
+        assert_eq!(builder().competitor_two(shane.clone()).build().err(), Some(MatchConfigError::SameCompetitor));
+        assert_eq!(builder().competitor_two(shane.clone().with_athlete_id("1")).competitor_one(ronaldo.clone().with_athlete_id("1")).build().err(),
+            Some(MatchConfigError::SameCompetitor));
+        assert_eq!(builder().mat_number(0).build().err(), Some(MatchConfigError::InvalidMat { mat_number: 0, mat_count: 20 }));
+        assert!(builder().mat_number(21).mat_count(24).build().is_ok());
+        assert_eq!(builder().fight_number(0).build().err(), Some(MatchConfigError::InvalidFightNumber));
+
+        let kid = Competitor::new("Kid", "Competitor", "BJJ Team", Country::Australia)
+            .with_division(Belt::Grey, AgeDivision::Kids, WeightClass::Light, Uniform::Gi);
+        assert_eq!(builder().competitor_one(kid.clone().with_athlete_id("1")).competitor_two(kid.clone().with_athlete_id("2")).match_time_minutes(10).build().err(),
+            Some(MatchConfigError::DurationOutOfRange { minutes: 10, division: AgeDivision::Kids, min: 2, max: 6 }));
+        assert_eq!(builder().competitor_two(kid).build().err(), Some(MatchConfigError::MixedAgeDivisions(AgeDivision::Adult, AgeDivision::Kids)));
+
+        let master = ronaldo.clone().with_division(Belt::Black, AgeDivision::Master1, WeightClass::Light, Uniform::Gi);
+        let adult = shane.clone().with_division(Belt::Black, AgeDivision::Adult, WeightClass::Light, Uniform::Gi);
+        assert_eq!(BJJMatch::builder().competitor_one(adult).competitor_two(master).build().unwrap().info.match_time_minutes, 6);
+    }
+
+    #[test]
+    fn test_builder_durations() {
+        let black_belt = |last_name| Competitor::new("Black", last_name, "BJJ Team", Country::Australia)
+            .with_division(Belt::Black, AgeDivision::Adult, WeightClass::Heavy, Uniform::Gi);
+        let bjj_match = BJJMatch::builder()
+            .competitor_one(black_belt("One"))
+            .competitor_two(black_belt("Two"))
+            .build()
+            .unwrap();
+        assert_eq!(bjj_match.info.match_time_minutes, 10);
+
+        let custom = DurationTable::from_json(r#"{"entries": [{"age_division": "Adult", "belt": null, "minutes": 3}], "default_minutes": 4}"#).unwrap();
+        let bjj_match = BJJMatch::builder().durations(custom).build().unwrap();
+        assert_eq!(bjj_match.info.match_time_minutes, 3);
+        assert_eq!(BJJMatch::builder().match_time_minutes(7).build().unwrap().info.match_time_minutes, 7);
+    }
+
+    #[test]
+    fn test_mercy_rule() {
+        let (bjj_match, _clock) = timed_match();
+        let mut bjj_match = bjj_match.with_mercy_rule(8);
+        bjj_match.add_points(4, CompetitorNumber::Two);
+        bjj_match.add_points(3, CompetitorNumber::One);
+        bjj_match.add_points(4, CompetitorNumber::Two);
+        assert!(bjj_match.result().is_none());
+        bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::Two);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::Two, criterion: WinCriterion::Finish(FinishMethod::PointsMargin) }));
+        assert!(!bjj_match.clock().is_running());
+        assert_eq!(bjj_match.try_add_points(2, CompetitorNumber::One).err().map(|error| matches!(error, MatchError::InvalidState(_))), Some(true));
+
+        bjj_match.undo();
+        assert_eq!(bjj_match.get_match_state(), MatchState::Running);
+
+        assert_eq!(BJJMatch::builder().mercy_rule(0).build().err(), Some(MatchConfigError::InvalidMercyMargin));
+        assert_eq!(BJJMatch::builder().mercy_rule(10).build().unwrap().mercy_rule(), Some(10));
+
+        let (bjj_match, _clock) = timed_match();
+        let mut bjj_match = bjj_match.with_mercy_rule(0);
+        assert_eq!(bjj_match.mercy_rule(), None);
+        bjj_match.add_points(2, CompetitorNumber::One);
+        assert!(bjj_match.result().is_none());
+
+        let (bjj_match, _clock) = timed_match();
+        let mut bjj_match = bjj_match.with_mercy_rule(8);
+        bjj_match.add_points(6, CompetitorNumber::One);
+        for _ in 0..3 {
+            bjj_match.add_penalty(CompetitorNumber::Two);
+        }
+        assert_eq!(bjj_match.score.competitor_one_score.points, 8);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::One, criterion: WinCriterion::Finish(FinishMethod::PointsMargin) }));
+    }
+
+    #[test]
+    fn test_infractions() {
+        let (mut bjj_match, _clock) = timed_match();
+        bjj_match.add_infraction(CompetitorNumber::One, Infraction::Stalling);
+        bjj_match.add_infraction(CompetitorNumber::One, Infraction::LeavingTheMat);
+        let one = &bjj_match.score.competitor_one_score;
+        let two = &bjj_match.score.competitor_two_score;
+        assert_eq!(one.penalties, 2);
+        assert_eq!(one.infractions, vec![Infraction::Stalling, Infraction::LeavingTheMat]);
+        assert_eq!((two.points, two.advantages), (2, 1));
+
+        let actions: Vec<MatchAction> = bjj_match.events().iter().skip(1).map(|event| event.action.clone()).collect();
+        assert_eq!(actions, vec![
+            MatchAction::Infraction { infraction: Infraction::Stalling, consequence: InfractionConsequence::Penalty },
+            MatchAction::Penalty,
+            MatchAction::Infraction { infraction: Infraction::LeavingTheMat, consequence: InfractionConsequence::PenaltyAndOpponentPoints(2) },
+            MatchAction::Penalty
+        ]);
+
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::SeriousFoul);
+        assert_eq!(bjj_match.result(), Some(MatchResult { winner: CompetitorNumber::One, criterion: WinCriterion::Finish(FinishMethod::Disqualification) }));
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 0);
+
+        let (bjj_match, _clock) = timed_match();
+        let mut bjj_match = bjj_match.with_rules(Box::new(Adcc));
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::Stalling);
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 0);
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::Stalling);
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 1);
+        assert_eq!(bjj_match.score.competitor_two_score.infraction_count(Infraction::Stalling), 2);
+
+        // Leaving the mat as the third penalty gives the opponent two points for the infraction
+        // and another two from the penalty escalation.
+        let (mut bjj_match, _clock) = timed_match();
+        bjj_match.add_penalty(CompetitorNumber::One);
+        bjj_match.add_penalty(CompetitorNumber::One);
+        assert_eq!(bjj_match.infraction_consequence(CompetitorNumber::One, Infraction::LeavingTheMat), InfractionConsequence::PenaltyAndOpponentPoints(2));
+        bjj_match.add_infraction(CompetitorNumber::One, Infraction::LeavingTheMat);
+        assert_eq!(bjj_match.score.competitor_one_score.penalties, 3);
+        assert_eq!(bjj_match.score.competitor_two_score.points, 4);
+        assert_eq!(bjj_match.score.competitor_two_score.technique_breakdown().last().map(|entry| entry.points), Some(4));
+        assert_eq!(bjj_match.infraction_consequence(CompetitorNumber::One, Infraction::SeriousFoul), InfractionConsequence::Disqualification);
+
+        // Removing the penalty takes the infraction and both lots of points back off.
+        bjj_match.subtract_penalty(CompetitorNumber::One);
+        assert_eq!(bjj_match.score.competitor_one_score.penalties, 2);
+        assert!(bjj_match.score.competitor_one_score.infractions.is_empty());
+        assert_eq!(bjj_match.score.competitor_two_score.points, 0);
+
+        let (bjj_match, _clock) = timed_match();
+        let mut bjj_match = bjj_match.with_rules(Box::new(Adcc));
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::Stalling);
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::Stalling);
+        bjj_match.add_infraction(CompetitorNumber::Two, Infraction::Stalling);
+        bjj_match.subtract_penalty(CompetitorNumber::Two);
+        bjj_match.subtract_penalty(CompetitorNumber::Two);
+        assert_eq!(bjj_match.score.competitor_two_score.penalties, 0);
+        assert_eq!(bjj_match.score.competitor_two_score.infractions, vec![Infraction::Stalling]);
+        assert_eq!(bjj_match.infraction_consequence(CompetitorNumber::Two, Infraction::Stalling), InfractionConsequence::Penalty);
+    }
+
+    #[test]
+    fn test_timer_events() {
+        let clock = MockClock::new();
+        let mut bjj_match = BJJMatch::new(Competitor::default(), Competitor::default(), 1, 1, 1)
+            .with_clock(Arc::new(clock.clone()));
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&observed);
+        bjj_match.add_timer_observer(Box::new(move |event| sink.borrow_mut().push(event)));
+
+        bjj_match.update();
+        bjj_match.start();
+        clock.advance_millis(25_000);
+        bjj_match.update();
+        clock.advance_millis(25_000);
+        bjj_match.update();
+        clock.advance_millis(10_000);
+        bjj_match.update();
+        bjj_match.update();
+
+        assert_eq!(*observed.borrow(), vec![
+            TimerEvent::Started,
+            TimerEvent::Warning { remaining_millis: 30_000 },
+            TimerEvent::Warning { remaining_millis: 10_000 },
+            TimerEvent::Expired
+        ]);
+    }
+}
@@ -1,20 +1,31 @@
 
-use std::io::{stdout, Write};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, stdout, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use crossterm::terminal::{Clear, ClearType, disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::{event, ExecutableCommand, QueueableCommand};
-use crossterm::event::{Event, KeyCode};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use crossterm::style::Print;
-use bjj_scoreboard::{BJJMatch, Competitor, CompetitorNumber, Country, MatchInformation, MatchState};
+use bjj_scoreboard::{rules, AgeDivision, AthleteId, Belt, BJJMatch, Competitor, CompetitorNumber, Country, DurationTable, EbiOutcome, EbiStartPosition, Infraction, InfractionConsequence, MatchConfigError, MatchInformation, MatchPeriod, MatchState, PlayerScore, RuleSet, ScoreField, ScoringAction, TimeoutKind, TimerEvent, Uniform, WeightClass};
 use eframe::egui;
-use eframe::egui::{Align2, Color32, FontFamily, Key, Pos2, Rounding};
+use eframe::egui::{Align2, Color32, Key, Modifiers, Pos2, Rounding};
 use eframe::emath::Rect;
-use egui_extras::Size;
-use egui_grid::GridBuilder;
-use crate::AppState::{NewMatchDialog, Normal};
+use egui_extras::RetainedImage;
+use crate::AppState::NewMatchDialog;
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_MERCY_MARGIN: usize = 15;
+
+/// Runs the egui scoreboard, or the terminal scoreboard with `--tui`.
+fn main() -> Result<()> {
+    if env::args().any(|arg| arg == "--tui") {
+        return app();
+    }
 
-fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         initial_window_size: Some(egui::vec2(640.0, 480.0)),
         ..Default::default()
@@ -22,11 +33,12 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "BJJ Scoreboard",
         options,
-        Box::new(|_cc| Box::<BjjScoreboard>::default())
-    )
+        Box::new(|_cc| Box::new(BjjScoreboard::new()))
+    ).map_err(|error| anyhow!("{}", error))
 }
 
 enum AppState {
+    ResumeMatchDialog,
     NewMatchDialog,
     Normal
 }
@@ -38,14 +50,25 @@ struct BjjScoreboard {
     first_run: bool,
     color_scheme: ColorScheme,
     font_sizes: FontSizes,
+    set_time_input: Option<String>,
+    /// An infraction that would disqualify the competitor, waiting for the operator to confirm it.
+    pending_disqualification: Option<(CompetitorNumber, Infraction)>,
+    /// The competitor the finish dialog would declare the winner, and the submission name typed so far.
+    pending_finish: Option<(CompetitorNumber, String)>,
+    clock_flash_until: Option<Instant>,
+    saved_match: Option<BJJMatch>,
+    autosave: Autosave,
+    /// Decoded flag images, loaded the first time each country is shown. `None` if the country
+    /// has no bundled flag.
+    flags: HashMap<Country, Option<RetainedImage>>,
+    config_error: Option<MatchConfigError>,
+    durations: DurationTable,
 }
 
 struct FontSizes {
     competitor_name: f32,
     competitor_team: f32,
-    competitor_adv_label: f32,
     competitor_adv: f32,
-    competitor_pen_label: f32,
     competitor_pen: f32,
     competitor_points: f32,
     time: f32,
@@ -58,9 +81,7 @@ impl Default for FontSizes {
         Self {
             competitor_name: 32.0,
             competitor_team: 32.0,
-            competitor_adv_label: 32.0,
             competitor_adv: 32.0,
-            competitor_pen_label: 32.0,
             competitor_pen: 32.0,
             competitor_points: 32.0,
             time: 32.0,
@@ -70,12 +91,6 @@ impl Default for FontSizes {
     }
 }
 
-struct DrawableComponent {
-    rect: Rect,
-    font_size: f32,
-    text: String,
-}
-
 struct ColorScheme {
     competitor_one_bg: Color32,
     competitor_one_name: Color32,
@@ -95,7 +110,6 @@ struct ColorScheme {
     competitor_two_pen: Color32,
     competitor_two_points_bg: Color32,
     competitor_two_points: Color32,
-    bottom_pane_bg: Color32,
     time: Color32,
     fight_info_heading: Color32,
     fight_info_sub_heading: Color32,
@@ -122,7 +136,6 @@ impl Default for ColorScheme {
             competitor_two_pen: Color32::from_rgb(255, 255, 255),
             competitor_two_points_bg: Color32::from_rgb(46, 100, 209),
             competitor_two_points: Color32::from_rgb(255, 255, 255),
-            bottom_pane_bg: Color32::from_rgb(0, 160, 0),
             time: Color32::from_rgb(255, 255, 180),
             fight_info_heading: Color32::from_rgb(200, 200, 140),
             fight_info_sub_heading: Color32::from_rgb(255, 255, 255),
@@ -139,6 +152,15 @@ impl Default for BjjScoreboard {
             first_run: true,
             color_scheme: Default::default(),
             font_sizes: Default::default(),
+            set_time_input: None,
+            pending_disqualification: None,
+            pending_finish: None,
+            clock_flash_until: None,
+            saved_match: None,
+            autosave: Autosave::new(autosave_path()),
+            flags: HashMap::new(),
+            config_error: None,
+            durations: DurationTable::default(),
         }
     }
 }
@@ -150,11 +172,15 @@ impl eframe::App for BjjScoreboard {
             self.first_run = false;
         }
         match self.app_state {
+            AppState::ResumeMatchDialog => {
+                self.draw_resume_match_modal(ctx)
+            },
             AppState::NewMatchDialog => {
                 self.draw_new_match_modal(ctx)
             },
             AppState::Normal => {
                 self.draw_active_match_screen(ctx);
+                self.autosave.update(&self.bjj_match);
                 ctx.request_repaint();
             }
         }
@@ -162,6 +188,36 @@ impl eframe::App for BjjScoreboard {
 }
 
 impl BjjScoreboard {
+    /// Starts with the "resume previous match" prompt if an unfinished match was autosaved.
+    fn new() -> Self {
+        let saved_match = BJJMatch::recover(autosave_path())
+            .ok()
+            .filter(|saved| !matches!(saved.get_match_state(), MatchState::NotStarted | MatchState::Finished(_)));
+
+        let durations = match DurationTable::load(durations_path()) {
+            Ok(durations) => durations,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    eprintln!("Couldn't load the match duration table, using IBJJF durations: {}", error);
+                }
+                DurationTable::default()
+            }
+        };
+
+        let mut scoreboard = match saved_match {
+            Some(saved_match) => Self {
+                app_state: AppState::ResumeMatchDialog,
+                saved_match: Some(saved_match),
+                ..Default::default()
+            },
+            None => Self::default()
+        };
+        let info = &mut scoreboard.bjj_match.info;
+        info.match_time_minutes = durations.minutes_for(&info.competitor_one, &info.competitor_two);
+        scoreboard.durations = durations;
+        scoreboard
+    }
+
     fn setup(&mut self, ctx: &egui::Context) {
 
         let mut fonts = egui::FontDefinitions::default();
@@ -204,29 +260,109 @@ impl BjjScoreboard {
         ui.end_row();
 
         let country = ui.label("Country");
+        let mut search: String = ui.data_mut(|data| data.get_temp(country.id)).unwrap_or_default();
         egui::ComboBox::from_id_source(country.id)
-            .selected_text(format!("{:?}", competitor.country))
+            .selected_text(competitor.country.name())
+            .show_ui(ui, |ui| {
+                ui.style_mut().wrap = Some(false);
+                ui.set_min_width(60.0);
+                ui.add(egui::TextEdit::singleline(&mut search).hint_text("Search")).request_focus();
+                egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    for candidate in Country::ALL.iter().filter(|candidate| candidate.matches_search(&search)) {
+                        ui.selectable_value(&mut competitor.country, *candidate, format!("{} ({})", candidate.name(), candidate.alpha3()));
+                    }
+                });
+            });
+        ui.data_mut(|data| data.insert_temp(country.id, search));
+        ui.end_row();
+
+        Self::draw_choice("Belt", &mut competitor.belt, Belt::ALL, ui);
+        Self::draw_choice("Age Division", &mut competitor.age_division, AgeDivision::ALL, ui);
+        Self::draw_choice("Weight Class", &mut competitor.weight_class, WeightClass::ALL, ui);
+        Self::draw_choice("Uniform", &mut competitor.uniform, Uniform::ALL, ui);
+
+        let athlete_id = ui.label("Athlete ID");
+        let mut id_text = competitor.athlete_id.as_ref().map(|id| id.0.clone()).unwrap_or_default();
+        if ui.text_edit_singleline(&mut id_text).labelled_by(athlete_id.id).changed() {
+            let id_text = id_text.trim();
+            competitor.athlete_id = (!id_text.is_empty()).then(|| AthleteId(id_text.to_owned()));
+        }
+        ui.end_row();
+    }
+
+    fn draw_choice<T: Copy + PartialEq + std::fmt::Display>(label: &str, value: &mut T, options: &[T], ui: &mut egui::Ui) {
+        let label = ui.label(label);
+        egui::ComboBox::from_id_source(label.id)
+            .selected_text(value.to_string())
             .show_ui(ui, |ui| {
                 ui.style_mut().wrap = Some(false);
                 ui.set_min_width(60.0);
-                ui.selectable_value(&mut competitor.country, Country::Australia, "Australia");
-                ui.selectable_value(&mut competitor.country, Country::Brazil, "Brazil");
+                for option in options {
+                    ui.selectable_value(value, *option, option.to_string());
+                }
             });
         ui.end_row();
     }
 
     fn draw_active_match_screen(&mut self, ctx: &egui::Context) {
+        for event in self.bjj_match.update() {
+            if matches!(event, TimerEvent::Warning { .. } | TimerEvent::Expired) {
+                self.clock_flash_until = Some(Instant::now() + Duration::from_secs(2));
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             self.ui(ui);
 
+            if self.set_time_input.is_some() {
+                self.draw_set_time_dialog(ctx);
+                return;
+            }
+            if self.pending_disqualification.is_some() {
+                self.draw_disqualification_dialog(ctx);
+                return;
+            }
+            if self.pending_finish.is_some() {
+                self.draw_finish_dialog(ctx);
+                return;
+            }
+
+            if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z) || i.consume_key(Modifiers::COMMAND, Key::Y)) {
+                self.bjj_match.redo();
+            }
+            if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Z)) {
+                self.bjj_match.undo();
+            }
+
+            // Shift with the first four scoring keys calls an infraction, consumed here so the
+            // key doesn't also score.
+            let infraction_keys = [
+                (Key::Q, CompetitorNumber::One), (Key::W, CompetitorNumber::One), (Key::E, CompetitorNumber::One), (Key::R, CompetitorNumber::One),
+                (Key::A, CompetitorNumber::Two), (Key::S, CompetitorNumber::Two), (Key::D, CompetitorNumber::Two), (Key::F, CompetitorNumber::Two),
+            ];
+            for (index, (key, competitor)) in infraction_keys.into_iter().enumerate() {
+                if ctx.input_mut(|i| i.consume_key(Modifiers::SHIFT, key)) {
+                    self.call_infraction(competitor, Infraction::ALL[index % 4]);
+                }
+            }
+
             if ctx.input(|i| i.key_pressed(Key::Q)) {
-                self.bjj_match.add_points(2, CompetitorNumber::One);
+                self.bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::One);
             }
             if ctx.input(|i| i.key_pressed(Key::W)) {
-                self.bjj_match.add_points(3, CompetitorNumber::One);
+                self.bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::One);
             }
             if ctx.input(|i| i.key_pressed(Key::E)) {
-                self.bjj_match.add_points(4, CompetitorNumber::One);
+                self.bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::One);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num1)) {
+                self.bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::One);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num2)) {
+                self.bjj_match.score_action(ScoringAction::KneeOnBelly, CompetitorNumber::One);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num3)) {
+                self.bjj_match.score_action(ScoringAction::BackTake, CompetitorNumber::One);
             }
             if ctx.input(|i| i.key_pressed(Key::R)) {
                 self.bjj_match.add_advantage( CompetitorNumber::One);
@@ -243,16 +379,28 @@ impl BjjScoreboard {
             if ctx.input(|i| i.key_pressed(Key::I)) {
                 self.bjj_match.subtract_penalty( CompetitorNumber::One);
             }
+            if ctx.input(|i| i.key_pressed(Key::O)) {
+                self.bjj_match.add_negative_point( CompetitorNumber::One);
+            }
 
 
             if ctx.input(|i| i.key_pressed(Key::A)) {
-                self.bjj_match.add_points(2, CompetitorNumber::Two);
+                self.bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::Two);
             }
             if ctx.input(|i| i.key_pressed(Key::S)) {
-                self.bjj_match.add_points(3, CompetitorNumber::Two);
+                self.bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::Two);
             }
             if ctx.input(|i| i.key_pressed(Key::D)) {
-                self.bjj_match.add_points(4, CompetitorNumber::Two);
+                self.bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::Two);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num4)) {
+                self.bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::Two);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num5)) {
+                self.bjj_match.score_action(ScoringAction::KneeOnBelly, CompetitorNumber::Two);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Num6)) {
+                self.bjj_match.score_action(ScoringAction::BackTake, CompetitorNumber::Two);
             }
             if ctx.input(|i| i.key_pressed(Key::F)) {
                 self.bjj_match.add_advantage( CompetitorNumber::Two);
@@ -269,16 +417,248 @@ impl BjjScoreboard {
             if ctx.input(|i| i.key_pressed(Key::K)) {
                 self.bjj_match.subtract_penalty( CompetitorNumber::Two);
             }
+            if ctx.input(|i| i.key_pressed(Key::L)) {
+                self.bjj_match.add_negative_point( CompetitorNumber::Two);
+            }
+
+            if ctx.input(|i| i.key_pressed(Key::N)) {
+                self.bjj_match.start_overtime();
+            }
+            if ctx.input(|i| i.key_pressed(Key::B)) {
+                self.bjj_match.start_ebi_turn(EbiStartPosition::BackControl);
+            }
+            if ctx.input(|i| i.key_pressed(Key::V)) {
+                self.bjj_match.start_ebi_turn(EbiStartPosition::SpiderWeb);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Z)) {
+                self.bjj_match.end_ebi_turn(EbiOutcome::Submission);
+            }
+            if ctx.input(|i| i.key_pressed(Key::X)) {
+                self.bjj_match.end_ebi_turn(EbiOutcome::Escape);
+            }
+            if ctx.input(|i| i.key_pressed(Key::C)) {
+                self.bjj_match.end_ebi_turn(EbiOutcome::TimeExpired);
+            }
 
             if ctx.input(|i| i.key_pressed(Key::Space)) {
                 self.bjj_match.toggle_start_stop();
             }
+
+            let timeout_keys = [
+                (Key::F1, CompetitorNumber::One, TimeoutKind::Injury),
+                (Key::F2, CompetitorNumber::One, TimeoutKind::Blood),
+                (Key::F3, CompetitorNumber::One, TimeoutKind::Equipment),
+                (Key::F4, CompetitorNumber::Two, TimeoutKind::Injury),
+                (Key::F5, CompetitorNumber::Two, TimeoutKind::Blood),
+                (Key::F6, CompetitorNumber::Two, TimeoutKind::Equipment),
+            ];
+            for (key, competitor, kind) in timeout_keys {
+                if ctx.input(|i| i.key_pressed(key)) {
+                    toggle_timeout(&mut self.bjj_match, competitor, kind);
+                }
+            }
+
+            for (key, competitor) in [(Key::F7, CompetitorNumber::One), (Key::F8, CompetitorNumber::Two)] {
+                if ctx.input(|i| i.key_pressed(key)) {
+                    self.pending_finish = Some((competitor, String::new()));
+                }
+            }
+
+            if ctx.input(|i| i.key_pressed(Key::ArrowUp)) {
+                self.bjj_match.add_time(1_000);
+            }
+            if ctx.input(|i| i.key_pressed(Key::ArrowDown)) {
+                self.bjj_match.subtract_time(1_000);
+            }
+            if ctx.input(|i| i.key_pressed(Key::PageUp)) {
+                self.bjj_match.add_time(10_000);
+            }
+            if ctx.input(|i| i.key_pressed(Key::PageDown)) {
+                self.bjj_match.subtract_time(10_000);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Home)) {
+                self.bjj_match.reset_clock();
+            }
+            if ctx.input(|i| i.key_pressed(Key::End)) {
+                refill_clock(&mut self.bjj_match);
+            }
+            if ctx.input(|i| i.key_pressed(Key::Enter)) {
+                self.set_time_input = Some(format_minutes_seconds(self.bjj_match.clock().get_remaining_time_milliseconds()));
+            }
             
 
         });
     }
 
 
+    /// The clock colour, blinking red for a couple of seconds after a warning or expiry.
+    fn time_color(&self) -> Color32 {
+        match self.clock_flash_until {
+            Some(until) if Instant::now() < until && (until - Instant::now()).as_millis() % 500 < 250 => Color32::RED,
+            _ => self.color_scheme.time
+        }
+    }
+
+    /// Records an infraction, unless it would disqualify the competitor, in which case it waits
+    /// for confirmation.
+    fn call_infraction(&mut self, competitor: CompetitorNumber, infraction: Infraction) {
+        match self.bjj_match.infraction_consequence(competitor, infraction) {
+            InfractionConsequence::Disqualification => self.pending_disqualification = Some((competitor, infraction)),
+            _ => self.bjj_match.add_infraction(competitor, infraction)
+        }
+    }
+
+    fn draw_disqualification_dialog(&mut self, ctx: &egui::Context) {
+        let Some((competitor, infraction)) = self.pending_disqualification else {
+            return;
+        };
+
+        let mut close = false;
+        egui::Window::new("Confirm Disqualification")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("Disqualify {} for {}?", self.bjj_match.competitor(competitor).get_display_name(), infraction));
+                ui.horizontal(|ui| {
+                    if ui.button("Disqualify").clicked() || ui.input(|i| i.key_pressed(Key::Enter)) {
+                        self.bjj_match.add_infraction(competitor, infraction);
+                        close = true;
+                    }
+                    if ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(Key::Escape)) {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.pending_disqualification = None;
+        }
+    }
+
+    /// Ends the match in the chosen competitor's favour: by a named submission, by the opponent's
+    /// disqualification, injury or absence, or by referee decision once one is awaited.
+    fn draw_finish_dialog(&mut self, ctx: &egui::Context) {
+        let Some((winner, submission_name)) = &mut self.pending_finish else {
+            return;
+        };
+        let winner = *winner;
+
+        let mut close = false;
+        egui::Window::new("Finish Match")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(format!("Win for {} by", self.bjj_match.competitor(winner).get_display_name()));
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(submission_name).request_focus();
+                    let named = !submission_name.trim().is_empty();
+                    if ui.add_enabled(named, egui::Button::new("Submission")).clicked() || (named && ui.input(|i| i.key_pressed(Key::Enter))) {
+                        self.bjj_match.submission(winner, submission_name.trim());
+                        close = true;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Disqualification").clicked() {
+                        self.bjj_match.disqualify(winner.opponent());
+                        close = true;
+                    }
+                    if ui.button("Injury").clicked() {
+                        self.bjj_match.medical_stoppage(winner.opponent());
+                        close = true;
+                    }
+                    if ui.button("Walkover").clicked() {
+                        self.bjj_match.walkover(winner);
+                        close = true;
+                    }
+                    let awaiting_decision = self.bjj_match.get_match_state() == MatchState::AwaitingDecision;
+                    if ui.add_enabled(awaiting_decision, egui::Button::new("Referee Decision")).clicked() {
+                        self.bjj_match.set_referee_decision(winner);
+                        close = true;
+                    }
+                    if ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(Key::Escape)) {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.pending_finish = None;
+        }
+    }
+
+    fn draw_set_time_dialog(&mut self, ctx: &egui::Context) {
+        let Some(input) = &mut self.set_time_input else {
+            return;
+        };
+
+        let mut close = false;
+        egui::Window::new("Set Remaining Time")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("Remaining time (m:ss)");
+                ui.text_edit_singleline(input).request_focus();
+                ui.horizontal(|ui| {
+                    if ui.button("Set").clicked() || ui.input(|i| i.key_pressed(Key::Enter)) {
+                        if let Some(millis) = parse_minutes_seconds(input) {
+                            self.bjj_match.set_remaining_time(millis);
+                        }
+                        close = true;
+                    }
+                    if ui.button("Cancel").clicked() || ui.input(|i| i.key_pressed(Key::Escape)) {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.set_time_input = None;
+        }
+    }
+
+    /// Draws the team name, then a swatch of the competitor's belt colour and their division.
+    fn draw_team_line(&self, ui: &mut egui::Ui, competitor: &Competitor, rect: Rect, color: Color32) {
+        let font = egui::FontId { size: self.font_sizes.competitor_name, ..Default::default()};
+        let team = ui.painter().text(rect.left_center(), Align2::LEFT_CENTER, competitor.team_name.as_str(), font.clone(), color);
+
+        let [r, g, b] = competitor.belt.rgb();
+        let swatch_height = font.size * 0.4;
+        let swatch = Rect::from_min_size(
+            Pos2::new(team.right() + font.size * 0.5, rect.center().y - swatch_height / 2.0),
+            egui::vec2(font.size * 1.5, swatch_height));
+        ui.painter().rect_filled(swatch, Rounding::same(2.0), Color32::from_rgb(r, g, b));
+
+        ui.painter().text(
+            Pos2::new(swatch.right() + font.size * 0.3, rect.center().y),
+            Align2::LEFT_CENTER,
+            competitor.get_division_name(),
+            font,
+            color);
+    }
+
+    /// Draws the country's flag scaled to fit `rect`, or its country code if there is no flag.
+    fn draw_flag(&mut self, ui: &mut egui::Ui, country: Country, rect: Rect, font_size: f32, text_color: Color32) {
+        let flag = self.flags.entry(country).or_insert_with(|| {
+            country.flag_png().and_then(|png| RetainedImage::from_image_bytes(country.alpha2(), png).ok())
+        });
+
+        match flag {
+            Some(image) => {
+                let size = image.size_vec2();
+                ui.painter().image(
+                    image.texture_id(ui.ctx()),
+                    fit_rect(rect.shrink(rect.width() * 0.1), size),
+                    Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                    Color32::WHITE);
+            },
+            None => {
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    country.alpha3(),
+                    egui::FontId { size: font_size, ..Default::default()},
+                    text_color);
+            }
+        }
+    }
+
     fn ui(&mut self, ui: &mut egui::Ui) {
         let match_grid = calc_grids(ui.clip_rect());
 
@@ -294,7 +674,7 @@ impl BjjScoreboard {
         ui.painter().rect_filled(match_grid.competitor_two.points, Rounding::none(), self.color_scheme.competitor_two_points_bg);
 
         let font = egui::FontId {
-            size: 32.0,
+            size: self.font_sizes.time,
             ..Default::default()
         };
 
@@ -305,31 +685,30 @@ impl BjjScoreboard {
             egui::FontId { size: self.font_sizes.competitor_name, ..Default::default()},
             self.color_scheme.competitor_one_name);
 
-        ui.painter().text(
-            match_grid.competitor_one.team.left_center(),
-            Align2::LEFT_CENTER,
-            self.bjj_match.info.competitor_one.team_name.as_str(),
-            egui::FontId { size: self.font_sizes.competitor_name, ..Default::default()},
+        self.draw_team_line(
+            ui,
+            &self.bjj_match.info.competitor_one,
+            match_grid.competitor_one.team,
             self.color_scheme.competitor_one_team);
 
         ui.painter().text(
             match_grid.competitor_one.advantages.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_one_score.advantages.to_string(),
+            score_text(self.bjj_match.rules(), &self.bjj_match.score.competitor_one_score, ScoreField::Advantages),
             egui::FontId { size: self.font_sizes.competitor_adv, ..Default::default()},
             self.color_scheme.competitor_one_adv);
 
         ui.painter().text(
             match_grid.competitor_one.penalties.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_one_score.penalties.to_string(),
+            score_text(self.bjj_match.rules(), &self.bjj_match.score.competitor_one_score, ScoreField::Penalties),
             egui::FontId { size: self.font_sizes.competitor_pen, ..Default::default()},
             self.color_scheme.competitor_one_pen);
 
         ui.painter().text(
             match_grid.competitor_one.points.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_one_score.points.to_string(),
+            points_text(&self.bjj_match, CompetitorNumber::One),
             egui::FontId { size: self.font_sizes.competitor_points, ..Default::default()},
             self.color_scheme.competitor_one_points);
 
@@ -340,61 +719,83 @@ impl BjjScoreboard {
             egui::FontId { size: self.font_sizes.competitor_name, ..Default::default()},
             self.color_scheme.competitor_two_name);
 
-        ui.painter().text(
-            match_grid.competitor_two.team.left_center(),
-            Align2::LEFT_CENTER,
-            self.bjj_match.info.competitor_two.team_name.as_str(),
-            egui::FontId { size: self.font_sizes.competitor_name, ..Default::default()},
+        self.draw_team_line(
+            ui,
+            &self.bjj_match.info.competitor_two,
+            match_grid.competitor_two.team,
             self.color_scheme.competitor_two_team);
 
         ui.painter().text(
             match_grid.competitor_two.advantages.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_two_score.advantages.to_string(),
+            score_text(self.bjj_match.rules(), &self.bjj_match.score.competitor_two_score, ScoreField::Advantages),
             egui::FontId { size: self.font_sizes.competitor_adv, ..Default::default()},
             self.color_scheme.competitor_two_adv);
 
         ui.painter().text(
             match_grid.competitor_two.penalties.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_two_score.penalties.to_string(),
+            score_text(self.bjj_match.rules(), &self.bjj_match.score.competitor_two_score, ScoreField::Penalties),
             egui::FontId { size: self.font_sizes.competitor_pen, ..Default::default()},
             self.color_scheme.competitor_two_pen);
 
         ui.painter().text(
             match_grid.competitor_two.points.center(),
             Align2::CENTER_CENTER,
-            self.bjj_match.score.competitor_two_score.points.to_string(),
+            points_text(&self.bjj_match, CompetitorNumber::Two),
             egui::FontId { size: self.font_sizes.competitor_points, ..Default::default()},
             self.color_scheme.competitor_two_points);
 
+        let flag_font_size = self.font_sizes.competitor_team;
+        let competitor_one_country = self.bjj_match.info.competitor_one.country;
+        self.draw_flag(ui, competitor_one_country, match_grid.competitor_one.flag, flag_font_size, self.color_scheme.competitor_one_team);
+        let competitor_two_country = self.bjj_match.info.competitor_two.country;
+        self.draw_flag(ui, competitor_two_country, match_grid.competitor_two.flag, flag_font_size, self.color_scheme.competitor_two_team);
 
-        // ui.painter().rect_filled(match_grid.competitor_one.flag, Rounding::none(), Color32::LIGHT_GREEN);
-        // ui.painter().rect_filled(match_grid.competitor_one.name, Rounding::none(), Color32::DARK_GREEN);
-        // ui.painter().rect_filled(match_grid.competitor_one.team, Rounding::none(), Color32::DARK_GRAY);
-        // ui.painter().rect_filled(match_grid.competitor_one.points, Rounding::none(), Color32::RED);
 
 
-        // ui.painter().rect_filled(match_grid.competitor_two.full, Rounding::none(), Color32::DARK_BLUE);
-
-        // ui.painter().rect_filled(match_grid.competitor_two.flag, Rounding::none(), Color32::BROWN);
-        // ui.painter().rect_filled(match_grid.competitor_two.name, Rounding::none(), Color32::YELLOW);
-        // ui.painter().rect_filled(match_grid.competitor_two.team, Rounding::none(), Color32::DARK_GRAY);
-        // ui.painter().rect_filled(match_grid.competitor_two.points, Rounding::none(), Color32::BLUE);
-
-        // ui.painter().rect_filled(match_grid.time.time, Rounding::none(), Color32::BLUE);
-        // ui.painter().rect_filled(match_grid.time.fight_info_heading, Rounding::none(), Color32::LIGHT_GREEN);
-        // ui.painter().rect_filled(match_grid.time.fight_info_sub_heading, Rounding::none(), Color32::GREEN);
-        // ui.painter().rect_filled(match_grid.time.logo, Rounding::none(), Color32::BROWN);
-
 
         ui.painter().text(
             match_grid.time.time.center(),
             Align2::CENTER_CENTER,
-            format_millis(self.bjj_match.time.get_remaining_time_milliseconds()),
+            format_millis(self.bjj_match.clock().get_display_time_milliseconds()),
             font,
-            self.color_scheme.time);
+            self.time_color());
+
+        for (competitor, grid) in [(CompetitorNumber::One, &match_grid.competitor_one), (CompetitorNumber::Two, &match_grid.competitor_two)] {
+            if let Some(text) = timeout_text(&self.bjj_match, competitor) {
+                ui.painter().rect_filled(grid.full, Rounding::none(), Color32::from_black_alpha(200));
+                ui.painter().text(
+                    grid.full.center(),
+                    Align2::CENTER_CENTER,
+                    text,
+                    egui::FontId { size: self.font_sizes.competitor_points, ..Default::default()},
+                    self.color_scheme.time);
+            }
+        }
 
+        if let Some(result) = self.bjj_match.result() {
+            ui.painter().text(
+                match_grid.time.fight_info_heading.left_center(),
+                Align2::LEFT_CENTER,
+                format!("{} wins", self.bjj_match.competitor(result.winner).get_display_name()),
+                egui::FontId { size: self.font_sizes.fight_info_heading, ..Default::default()},
+                self.color_scheme.fight_info_heading);
+
+            ui.painter().text(
+                match_grid.time.fight_info_sub_heading.left_center(),
+                Align2::LEFT_CENTER,
+                result.criterion.to_string(),
+                egui::FontId { size: self.font_sizes.fight_info_sub_heading, ..Default::default()},
+                self.color_scheme.fight_info_sub_heading);
+        } else if let Some(period) = period_text(&self.bjj_match) {
+            ui.painter().text(
+                match_grid.time.fight_info_heading.left_center(),
+                Align2::LEFT_CENTER,
+                period,
+                egui::FontId { size: self.font_sizes.fight_info_heading, ..Default::default()},
+                self.color_scheme.fight_info_heading);
+        }
 
     }
 
@@ -404,18 +805,88 @@ impl BjjScoreboard {
         ui.end_row();
 
         let match_time = ui.label("Match Duration (mins)");
-        ui.add(egui::DragValue::new(&mut info.match_time_minutes).speed(0.1).clamp_range(1..=30)).labelled_by(match_time.id);
+        ui.add(egui::DragValue::new(&mut info.match_time_minutes).speed(0.1)).labelled_by(match_time.id);
         ui.end_row();
 
         let mat_num = ui.label("Mat Number");
-        ui.add(egui::DragValue::new(&mut info.mat_number).speed(0.1).clamp_range(1..=20)).labelled_by(mat_num.id);
+        ui.add(egui::DragValue::new(&mut info.mat_number).speed(0.1)).labelled_by(mat_num.id);
         ui.end_row();
 
         let fight_num = ui.label("Fight Number");
-        ui.add(egui::DragValue::new(&mut info.fight_number).speed(0.1).clamp_range(1..=30)).labelled_by(fight_num.id);
+        ui.add(egui::DragValue::new(&mut info.fight_number).speed(0.1)).labelled_by(fight_num.id);
         ui.end_row();
     }
 
+    fn draw_rule_set_dialog(bjj_match: &mut BJJMatch, ui: &mut egui::Ui) {
+        let rule_set = ui.label("Rule Set");
+        let mut selected = bjj_match.rules().name();
+        egui::ComboBox::from_id_source(rule_set.id)
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.style_mut().wrap = Some(false);
+                ui.set_min_width(60.0);
+                for name in rules::PRESET_NAMES {
+                    ui.selectable_value(&mut selected, name, name);
+                }
+            });
+        if selected != bjj_match.rules().name() {
+            if let Some(rule_set) = rules::preset(selected) {
+                bjj_match.set_rules(rule_set);
+            }
+        }
+        ui.end_row();
+
+        let mercy = ui.label("Mercy Rule (points lead)");
+        ui.horizontal(|ui| {
+            let mut enabled = bjj_match.mercy_rule().is_some();
+            let mut margin = bjj_match.mercy_rule().unwrap_or(DEFAULT_MERCY_MARGIN);
+            ui.checkbox(&mut enabled, "").labelled_by(mercy.id);
+            ui.add_enabled(enabled, egui::DragValue::new(&mut margin).speed(0.1));
+            bjj_match.set_mercy_rule(enabled.then_some(margin));
+        });
+        ui.end_row();
+    }
+
+    fn draw_resume_match_modal(&mut self, ctx: &egui::Context) {
+        let Some(saved_match) = &self.saved_match else {
+            self.app_state = AppState::NewMatchDialog;
+            return;
+        };
+
+        let mut resume = None;
+        egui::Window::new("Resume Previous Match")
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let info = &saved_match.info;
+                ui.label(format!("Mat {}, Fight {}", info.mat_number, info.fight_number));
+                for competitor in [CompetitorNumber::One, CompetitorNumber::Two] {
+                    ui.label(format!("{}: {}", saved_match.competitor(competitor).get_display_name(), points_text(saved_match, competitor)));
+                }
+                ui.label(format!("Time remaining: {}", format_millis(saved_match.clock().get_display_time_milliseconds())));
+                ui.horizontal(|ui| {
+                    if ui.button("Resume").clicked() {
+                        resume = Some(true);
+                    }
+                    if ui.button("New Match").clicked() {
+                        resume = Some(false);
+                    }
+                });
+            });
+
+        match resume {
+            Some(true) => {
+                self.bjj_match = self.saved_match.take().unwrap_or_default();
+                self.app_state = AppState::Normal;
+            }
+            Some(false) => {
+                self.saved_match = None;
+                let _ = fs::remove_file(autosave_path());
+                self.app_state = AppState::NewMatchDialog;
+            }
+            None => {}
+        }
+    }
+
     fn draw_new_match_modal(&mut self, ctx: &egui::Context) {
         egui::Window::new("Match Settings")
             .open(&mut self.match_dialog_open)
@@ -425,18 +896,45 @@ impl BjjScoreboard {
                         .spacing([40.0, 4.0])
                         .striped(true)
                         .show(ui, |ui| {
+                            let info = &self.bjj_match.info;
+                            let division_minutes = self.durations.minutes_for(&info.competitor_one, &info.competitor_two);
                             BjjScoreboard::draw_competitor_dialog("Competitor One", &mut self.bjj_match.info.competitor_one, ui);
                             ui.separator();
                             ui.end_row();
                             BjjScoreboard::draw_competitor_dialog("Competitor Two", &mut self.bjj_match.info.competitor_two, ui);
+                            let info = &mut self.bjj_match.info;
+                            let new_division_minutes = self.durations.minutes_for(&info.competitor_one, &info.competitor_two);
+                            if new_division_minutes != division_minutes {
+                                info.match_time_minutes = new_division_minutes;
+                            }
                             ui.separator();
                             ui.end_row();
                             BjjScoreboard::draw_match_info_dialog("Match Information", &mut self.bjj_match.info, ui);
+                            BjjScoreboard::draw_rule_set_dialog(&mut self.bjj_match, ui);
                             ui.separator();
                             ui.end_row();
                             if ui.add(egui::Button::new("Start Match")).clicked() {
-                                self.app_state = AppState::Normal;
-                                self.bjj_match.start();
+                                let mut builder = BJJMatch::builder()
+                                    .info(self.bjj_match.info.clone())
+                                    .durations(self.durations.clone());
+                                if let Some(margin) = self.bjj_match.mercy_rule() {
+                                    builder = builder.mercy_rule(margin);
+                                }
+                                // The rule set only moves to the new match once the form is valid,
+                                // so a rejected form keeps it.
+                                let built = builder.validate().and_then(|()| builder.rules(self.bjj_match.take_rules()).build());
+                                match built {
+                                    Ok(bjj_match) => {
+                                        self.bjj_match = bjj_match;
+                                        self.config_error = None;
+                                        self.app_state = AppState::Normal;
+                                        self.bjj_match.start();
+                                    }
+                                    Err(error) => self.config_error = Some(error)
+                                }
+                            }
+                            if let Some(error) = &self.config_error {
+                                ui.colored_label(Color32::RED, error.to_string());
                             }
                         });
                 }
@@ -448,10 +946,16 @@ impl BjjScoreboard {
 
 
 fn app() -> Result<()> {
-    let shane = Competitor::new("Shane", "Poppleton", "Fight Club Jiu-Jitsu", Country::Australia);
-    let ronaldo = Competitor::new("Ronaldo", "Mendes Dos Santos", "Caza BJJ", Country::Brazil);
+    let shane = Competitor::new("Shane", "Poppleton", "Fight Club Jiu-Jitsu", Country::Australia)
+        .with_division(Belt::Black, AgeDivision::Adult, WeightClass::Light, Uniform::Gi);
+    let ronaldo = Competitor::new("Ronaldo", "Mendes Dos Santos", "Caza BJJ", Country::Brazil)
+        .with_division(Belt::Black, AgeDivision::Adult, WeightClass::Light, Uniform::Gi);
 
-    let mut bjj_match = BJJMatch::new(shane, ronaldo, 10, 1, 1);
+    let mut bjj_match = BJJMatch::builder()
+        .competitor_one(shane)
+        .competitor_two(ronaldo)
+        .match_time_minutes(10)
+        .build()?;
 
 
     bjj_match.start();
@@ -459,26 +963,114 @@ fn app() -> Result<()> {
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
 
-    loop {
+    // The remaining time being typed in after pressing Enter, as m:ss.
+    let mut set_time_input: Option<String> = None;
+    // An infraction that would disqualify the competitor, waiting for 'y' to confirm it.
+    let mut pending_disqualification: Option<(CompetitorNumber, Infraction)> = None;
+    // The competitor F7 or F8 would declare the winner, and the submission name once 's' is
+    // pressed and it is being typed in.
+    let mut pending_finish: Option<(CompetitorNumber, Option<String>)> = None;
+    let mut autosave = Autosave::new(tui_autosave_path());
 
-        let match_state = bjj_match.get_match_state();
+    loop {
 
-        if match_state == MatchState::Finished {
-            break;
+        for event in bjj_match.update() {
+            if matches!(event, TimerEvent::Warning { .. } | TimerEvent::Expired) {
+                stdout().queue(Print('\x07'))?;
+            }
         }
-
+        autosave.update(&bjj_match);
 
         if event::poll(Duration::from_millis(10))? {
             if let Event::Key(key_event) = event::read()? {
+                if let Some(input) = &mut set_time_input {
+                    match key_event.code {
+                        KeyCode::Char(c) if c.is_ascii_digit() || c == ':' => input.push(c),
+                        KeyCode::Backspace => {
+                            input.pop();
+                        }
+                        KeyCode::Enter => {
+                            if let Some(millis) = parse_minutes_seconds(input) {
+                                bjj_match.set_remaining_time(millis);
+                            }
+                            set_time_input = None;
+                        }
+                        KeyCode::Esc => set_time_input = None,
+                        _ => {}
+                    }
+                    draw_scoreboard(&bjj_match, prompt(&bjj_match, &set_time_input, pending_disqualification, &pending_finish))?;
+                    continue;
+                }
+                if let Some((winner, Some(submission_name))) = &mut pending_finish {
+                    match key_event.code {
+                        KeyCode::Char(c) => submission_name.push(c),
+                        KeyCode::Backspace => {
+                            submission_name.pop();
+                        }
+                        KeyCode::Enter if !submission_name.trim().is_empty() => {
+                            bjj_match.submission(*winner, submission_name.trim());
+                            pending_finish = None;
+                        }
+                        KeyCode::Esc => pending_finish = None,
+                        _ => {}
+                    }
+                    draw_scoreboard(&bjj_match, prompt(&bjj_match, &set_time_input, pending_disqualification, &pending_finish))?;
+                    continue;
+                }
+                if let Some((winner, None)) = pending_finish.take() {
+                    match key_event.code {
+                        KeyCode::Char('s') => pending_finish = Some((winner, Some(String::new()))),
+                        KeyCode::Char('d') => bjj_match.disqualify(winner.opponent()),
+                        KeyCode::Char('i') => bjj_match.medical_stoppage(winner.opponent()),
+                        KeyCode::Char('w') => bjj_match.walkover(winner),
+                        KeyCode::Char('r') => bjj_match.set_referee_decision(winner),
+                        _ => {}
+                    }
+                    draw_scoreboard(&bjj_match, prompt(&bjj_match, &set_time_input, pending_disqualification, &pending_finish))?;
+                    continue;
+                }
+                if let Some((competitor, infraction)) = pending_disqualification.take() {
+                    if key_event.code == KeyCode::Char('y') {
+                        bjj_match.add_infraction(competitor, infraction);
+                    }
+                    draw_scoreboard(&bjj_match, None)?;
+                    continue;
+                }
+
                 match key_event.code {
+                    KeyCode::Char('z') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        bjj_match.undo();
+                    }
+                    KeyCode::Char('y') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                        bjj_match.redo();
+                    }
+                    KeyCode::Char(key @ ('Q' | 'W' | 'E' | 'R' | 'A' | 'S' | 'D' | 'F'))
+                        if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+                        let index = "QWERASDF".find(key).unwrap_or_default();
+                        let competitor = if index < 4 { CompetitorNumber::One } else { CompetitorNumber::Two };
+                        let infraction = Infraction::ALL[index % 4];
+                        match bjj_match.infraction_consequence(competitor, infraction) {
+                            InfractionConsequence::Disqualification => pending_disqualification = Some((competitor, infraction)),
+                            _ => bjj_match.add_infraction(competitor, infraction)
+                        }
+                    }
                     KeyCode::Char('q') => {
-                        bjj_match.add_points(2, CompetitorNumber::One);
+                        bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::One);
                     }
                     KeyCode::Char('w') => {
-                        bjj_match.add_points(3, CompetitorNumber::One);
+                        bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::One);
                     }
                     KeyCode::Char('e') => {
-                        bjj_match.add_points(4, CompetitorNumber::One);
+                        bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::One);
+                    }
+                    KeyCode::Char('1') => {
+                        bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::One);
+                    }
+                    KeyCode::Char('2') => {
+                        bjj_match.score_action(ScoringAction::KneeOnBelly, CompetitorNumber::One);
+                    }
+                    KeyCode::Char('3') => {
+                        bjj_match.score_action(ScoringAction::BackTake, CompetitorNumber::One);
                     }
                     KeyCode::Char('r') => {
                         bjj_match.add_advantage(CompetitorNumber::One);
@@ -495,14 +1087,26 @@ fn app() -> Result<()> {
                     KeyCode::Char('i') => {
                         bjj_match.subtract_penalty(CompetitorNumber::One);
                     }
+                    KeyCode::Char('o') => {
+                        bjj_match.add_negative_point(CompetitorNumber::One);
+                    }
                     KeyCode::Char('a') => {
-                        bjj_match.add_points(2, CompetitorNumber::Two);
+                        bjj_match.score_action(ScoringAction::Takedown, CompetitorNumber::Two);
                     }
                     KeyCode::Char('s') => {
-                        bjj_match.add_points(3, CompetitorNumber::Two);
+                        bjj_match.score_action(ScoringAction::GuardPass, CompetitorNumber::Two);
                     }
                     KeyCode::Char('d') => {
-                        bjj_match.add_points(4, CompetitorNumber::Two);
+                        bjj_match.score_action(ScoringAction::Mount, CompetitorNumber::Two);
+                    }
+                    KeyCode::Char('4') => {
+                        bjj_match.score_action(ScoringAction::Sweep, CompetitorNumber::Two);
+                    }
+                    KeyCode::Char('5') => {
+                        bjj_match.score_action(ScoringAction::KneeOnBelly, CompetitorNumber::Two);
+                    }
+                    KeyCode::Char('6') => {
+                        bjj_match.score_action(ScoringAction::BackTake, CompetitorNumber::Two);
                     }
                     KeyCode::Char('f') => {
                         bjj_match.add_advantage(CompetitorNumber::Two);
@@ -519,9 +1123,66 @@ fn app() -> Result<()> {
                     KeyCode::Char('k') => {
                         bjj_match.subtract_penalty(CompetitorNumber::Two);
                     }
+                    KeyCode::Char('l') => {
+                        bjj_match.add_negative_point(CompetitorNumber::Two);
+                    }
+                    KeyCode::Char('n') => {
+                        bjj_match.start_overtime();
+                    }
+                    KeyCode::Char('b') => {
+                        bjj_match.start_ebi_turn(EbiStartPosition::BackControl);
+                    }
+                    KeyCode::Char('v') => {
+                        bjj_match.start_ebi_turn(EbiStartPosition::SpiderWeb);
+                    }
+                    KeyCode::Char('z') => {
+                        bjj_match.end_ebi_turn(EbiOutcome::Submission);
+                    }
+                    KeyCode::Char('x') => {
+                        bjj_match.end_ebi_turn(EbiOutcome::Escape);
+                    }
+                    KeyCode::Char('c') => {
+                        bjj_match.end_ebi_turn(EbiOutcome::TimeExpired);
+                    }
                     KeyCode::Char(' ') => {
                         bjj_match.toggle_start_stop();
                     }
+                    KeyCode::F(number @ 1..=6) => {
+                        let competitor = if number <= 3 { CompetitorNumber::One } else { CompetitorNumber::Two };
+                        let kind = match number % 3 {
+                            1 => TimeoutKind::Injury,
+                            2 => TimeoutKind::Blood,
+                            _ => TimeoutKind::Equipment
+                        };
+                        toggle_timeout(&mut bjj_match, competitor, kind);
+                    }
+                    KeyCode::F(7) => {
+                        pending_finish = Some((CompetitorNumber::One, None));
+                    }
+                    KeyCode::F(8) => {
+                        pending_finish = Some((CompetitorNumber::Two, None));
+                    }
+                    KeyCode::Up => {
+                        bjj_match.add_time(1_000);
+                    }
+                    KeyCode::Down => {
+                        bjj_match.subtract_time(1_000);
+                    }
+                    KeyCode::PageUp => {
+                        bjj_match.add_time(10_000);
+                    }
+                    KeyCode::PageDown => {
+                        bjj_match.subtract_time(10_000);
+                    }
+                    KeyCode::Home => {
+                        bjj_match.reset_clock();
+                    }
+                    KeyCode::End => {
+                        refill_clock(&mut bjj_match);
+                    }
+                    KeyCode::Enter => {
+                        set_time_input = Some(format_minutes_seconds(bjj_match.clock().get_remaining_time_milliseconds()));
+                    }
                     KeyCode::Esc => {
                         println!("Escape key is pressed. Exiting...");
                         break;
@@ -535,7 +1196,7 @@ fn app() -> Result<()> {
 
 
 
-        draw_scoreboard(&bjj_match)?;
+        draw_scoreboard(&bjj_match, prompt(&bjj_match, &set_time_input, pending_disqualification, &pending_finish))?;
     }
 
     stdout().execute(LeaveAlternateScreen)?;
@@ -552,7 +1213,29 @@ fn println_at(row: u16, text: String) -> Result<()> {
     Ok(())
 }
 
-fn draw_scoreboard(bjj_match: &BJJMatch) -> Result<()> {
+/// The line asking the operator for input, if the TUI is waiting for any.
+fn prompt(
+    bjj_match: &BJJMatch,
+    set_time_input: &Option<String>,
+    pending_disqualification: Option<(CompetitorNumber, Infraction)>,
+    pending_finish: &Option<(CompetitorNumber, Option<String>)>
+) -> Option<String> {
+    if let Some(input) = set_time_input {
+        return Some(format!("Set remaining time (m:ss): {}", input));
+    }
+    if let Some((winner, submission_name)) = pending_finish {
+        let name = bjj_match.competitor(*winner).get_display_name();
+        return Some(match submission_name {
+            Some(submission_name) => format!("Submission by {}: {}", name, submission_name),
+            None => format!("Win for {} by (s)ubmission, (d)isqualification, (i)njury, (w)alkover or (r)eferee decision?", name)
+        });
+    }
+    pending_disqualification.map(|(competitor, infraction)| {
+        format!("Disqualify {} for {}? (y/n)", bjj_match.competitor(competitor).get_display_name(), infraction)
+    })
+}
+
+fn draw_scoreboard(bjj_match: &BJJMatch, prompt: Option<String>) -> Result<()> {
     stdout().execute(Clear(ClearType::All))?;
     let comp1 = &bjj_match.info.competitor_one;
     let comp2 = &bjj_match.info.competitor_two;
@@ -563,12 +1246,175 @@ fn draw_scoreboard(bjj_match: &BJJMatch) -> Result<()> {
     println_at(1, format!("Points: {}    Advantages: {}    Penalties: {}", score1.points, score1.advantages, score1.penalties))?;
     println_at(3, format!("{} {}", comp2.first_name, comp2.last_name))?;
     println_at(4, format!("Points: {}    Advantages: {}    Penalties: {}", score2.points, score2.advantages, score2.penalties))?;
-    println_at(6, format!("Time remaining: {}", format_millis(bjj_match.time.get_remaining_time_milliseconds())))?;
+    println_at(6, format!("Time: {}", format_millis(bjj_match.clock().get_display_time_milliseconds())))?;
+
+    if let Some(period) = period_text(bjj_match) {
+        println_at(7, period)?;
+    }
+    for (row, competitor) in [(2, CompetitorNumber::One), (5, CompetitorNumber::Two)] {
+        if let Some(text) = timeout_text(bjj_match, competitor) {
+            println_at(row, text)?;
+        }
+    }
+    if bjj_match.ebi_overtime().is_some() {
+        println_at(9, format!("Overtime: {}    {}", points_text(bjj_match, CompetitorNumber::One), points_text(bjj_match, CompetitorNumber::Two)))?;
+    }
+    if let Some(result) = bjj_match.result() {
+        println_at(8, format!("Winner: {} by {}", bjj_match.competitor(result.winner).get_display_name(), result.criterion))?;
+    }
+    if let Some(prompt) = prompt {
+        println_at(10, prompt)?;
+    }
 
     stdout().flush()?;
     Ok(())
 }
 
+/// Puts the active clock back to the full length of the current period without stopping it.
+/// Count-up clocks go back to zero.
+fn refill_clock(bjj_match: &mut BJJMatch) {
+    let clock = bjj_match.clock();
+    let full_time = match clock.counts_up() {
+        true => 0,
+        false => clock.get_duration_milliseconds()
+    };
+    bjj_match.set_remaining_time(full_time);
+}
+
+fn toggle_timeout(bjj_match: &mut BJJMatch, competitor: CompetitorNumber, kind: TimeoutKind) {
+    match bjj_match.timeouts().active() {
+        Some(active) if active == (competitor, kind) => bjj_match.end_timeout(),
+        _ => bjj_match.start_timeout(competitor, kind)
+    }
+}
+
+fn timeout_text(bjj_match: &BJJMatch, competitor: CompetitorNumber) -> Option<String> {
+    let (active, kind) = bjj_match.timeouts().active()?;
+    if active != competitor {
+        return None;
+    }
+
+    let used = bjj_match.timeouts().competitor(competitor).get(kind).get_elapsed_time_milliseconds();
+    match bjj_match.timeouts().limits.get(kind) {
+        Some(limit) => Some(format!("{} {} / {}", kind, format_minutes_seconds(used), format_minutes_seconds(limit))),
+        None => Some(format!("{} {}", kind, format_minutes_seconds(used)))
+    }
+}
+
+/// Saves the active match whenever something is logged, including the clock starting or
+/// stopping, and every few seconds in between so the clock is never far behind.
+struct Autosave {
+    path: PathBuf,
+    last_saved: Instant,
+    saved_events: usize
+}
+
+impl Autosave {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_saved: Instant::now(),
+            saved_events: 0
+        }
+    }
+
+    fn update(&mut self, bjj_match: &BJJMatch) {
+        let logged = bjj_match.events().len() != self.saved_events;
+        if !logged && self.last_saved.elapsed() < AUTOSAVE_INTERVAL {
+            return;
+        }
+
+        if let Err(error) = bjj_match.save(&self.path) {
+            eprintln!("Autosave failed: {}", error);
+        }
+        self.last_saved = Instant::now();
+        self.saved_events = bjj_match.events().len();
+    }
+}
+
+/// Where the active match is autosaved, in the user's home directory when there is one.
+fn autosave_path() -> PathBuf {
+    home_file(".bjj_scoreboard_autosave.json")
+}
+
+/// Where the terminal scoreboard autosaves, kept apart so it never overwrites a match the
+/// window is waiting to resume.
+fn tui_autosave_path() -> PathBuf {
+    home_file(".bjj_scoreboard_tui_autosave.json")
+}
+
+/// A custom match duration table for local promotions. The IBJJF durations are used if there
+/// isn't one.
+fn durations_path() -> PathBuf {
+    home_file(".bjj_scoreboard_durations.json")
+}
+
+fn home_file(name: &str) -> PathBuf {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(name)
+}
+
+fn period_text(bjj_match: &BJJMatch) -> Option<String> {
+    if bjj_match.get_match_state() == MatchState::AwaitingDecision {
+        return Some("Awaiting Decision".to_owned());
+    }
+
+    if let Some(ebi) = bjj_match.ebi_overtime() {
+        return Some(format!("EBI Round {}", ebi.round()));
+    }
+
+    match bjj_match.period() {
+        MatchPeriod::Overtime(1) => Some("OVERTIME".to_owned()),
+        MatchPeriod::Overtime(period) => Some(format!("OVERTIME {}", period)),
+        MatchPeriod::Regulation if !bjj_match.points_allowed() && bjj_match.rules().uses_field(ScoreField::Points) => {
+            Some("No Points Period".to_owned())
+        }
+        MatchPeriod::Regulation => None
+    }
+}
+
+fn points_text(bjj_match: &BJJMatch, competitor: CompetitorNumber) -> String {
+    if let Some(ebi) = bjj_match.ebi_overtime() {
+        return format_millis(ebi.cumulative_escape_millis(competitor));
+    }
+
+    let score = match competitor {
+        CompetitorNumber::One => &bjj_match.score.competitor_one_score,
+        CompetitorNumber::Two => &bjj_match.score.competitor_two_score
+    };
+    score_text(bjj_match.rules(), score, ScoreField::Points)
+}
+
+fn score_text(rules: &dyn RuleSet, score: &PlayerScore, field: ScoreField) -> String {
+    if !rules.uses_field(field) {
+        return String::new();
+    }
+
+    match field {
+        ScoreField::Points => score.points.to_string(),
+        ScoreField::Advantages => score.advantages.to_string(),
+        ScoreField::Penalties => score.penalties.to_string()
+    }
+}
+
+fn format_minutes_seconds(millis: usize) -> String {
+    format!("{}:{:02}", millis / 60_000, (millis % 60_000) / 1_000)
+}
+
+fn parse_minutes_seconds(text: &str) -> Option<usize> {
+    let (minutes, seconds) = text.trim().split_once(':').unwrap_or(("0", text.trim()));
+    let minutes: usize = minutes.parse().ok()?;
+    let seconds: usize = seconds.parse().ok()?;
+    if seconds >= 60 {
+        return None;
+    }
+
+    minutes.checked_mul(60)?.checked_add(seconds)?.checked_mul(1_000)
+}
+
 pub fn format_millis(millis: usize) -> String {
     let hours = millis / 3_600_000;
     let minutes = (millis % 3_600_000) / 60_000;
@@ -588,19 +1434,14 @@ struct MatchGrid {
 
 #[derive(Debug)]
 struct TimeGrid {
-    full: Rect,
     time: Rect,
     fight_info_heading: Rect,
-    fight_info_sub_heading: Rect,
-    logo: Rect
+    fight_info_sub_heading: Rect
 }
 #[derive(Debug)]
 struct CompetitorGrid {
     full: Rect,
-    main: Rect,
     left: Rect,
-    right: Rect,
-    comp: Rect,
     flag: Rect,
     name: Rect,
     team: Rect,
@@ -627,16 +1468,14 @@ fn calc_grids(rect: Rect) -> MatchGrid {
 }
 
 fn calc_time_grid(rect: Rect) -> TimeGrid {
-    let (left, logo) = split_horizontal(rect, 5.0 / 6.0);
+    let (left, _logo) = split_horizontal(rect, 5.0 / 6.0);
     let (time, fight_info) = split_horizontal(left, 1.0 / 3.0);
     let (fight_info_heading, fight_info_sub_heading) = split_vertical(fight_info, 0.5);
 
     TimeGrid {
-        full: rect,
         time,
         fight_info_heading,
-        fight_info_sub_heading,
-        logo
+        fight_info_sub_heading
     }
 }
 
@@ -649,9 +1488,7 @@ fn calc_competitor_grid(rect: Rect) -> CompetitorGrid {
 
     CompetitorGrid {
         full: rect,
-        main,
-        left,right,
-        comp,
+        left,
         flag,
         name,
         team,
@@ -661,6 +1498,12 @@ fn calc_competitor_grid(rect: Rect) -> CompetitorGrid {
     }
 }
 
+/// The largest rect with the aspect ratio of `size` that fits in `rect`, centred in it.
+fn fit_rect(rect: Rect, size: egui::Vec2) -> Rect {
+    let scale = (rect.width() / size.x).min(rect.height() / size.y);
+    Rect::from_center_size(rect.center(), size * scale)
+}
+
 fn split_horizontal(rect: Rect, at: f32) -> (Rect, Rect) {
     let new_x = rect.min.x + (rect.max.x - rect.min.x) * at;
 
@@ -670,14 +1513,6 @@ fn split_horizontal(rect: Rect, at: f32) -> (Rect, Rect) {
     )
 }
 
-fn split_fixed_horizontal(rect: Rect, at: f32) -> (Rect, Rect) {
-    let new_x = rect.min.x + at;
-
-    (
-        Rect::from_min_max(rect.min, Pos2::new(new_x, rect.max.y)),
-        Rect::from_min_max(Pos2::new(new_x, rect.min.y), rect.max)
-    )
-}
 
 fn split_vertical(rect: Rect, at: f32) -> (Rect, Rect) {
     let new_y = rect.min.y + (rect.max.y - rect.min.y) * at;
@@ -688,18 +1523,10 @@ fn split_vertical(rect: Rect, at: f32) -> (Rect, Rect) {
     )
 }
 
-fn split_fixed_vertical(rect: Rect, at: f32) -> (Rect, Rect) {
-    let new_y = rect.min.y + at;
-
-    (
-        Rect::from_min_max(rect.min, Pos2::new(rect.max.x, new_y)),
-        Rect::from_min_max(Pos2::new(rect.min.x, new_y), rect.max)
-    )
-}
 #[cfg(test)]
 mod tests {
     use eframe::egui::{Pos2, Rect};
-    use crate::split_vertical;
+    use crate::{parse_minutes_seconds, split_vertical};
 
     #[test]
     fn test_split_vertical() {
@@ -715,5 +1542,14 @@ mod tests {
         assert_eq!(rect1.max.x, 484.0);
         assert_eq!(rect1.max.y, 300.0);
     }
-}
 
+    #[test]
+    fn test_parse_minutes_seconds() {
+        assert_eq!(parse_minutes_seconds("4:05"), Some(245_000));
+        assert_eq!(parse_minutes_seconds("30"), Some(30_000));
+        assert_eq!(parse_minutes_seconds("a:00"), None);
+        assert_eq!(parse_minutes_seconds("1:99"), None);
+        assert_eq!(parse_minutes_seconds("99999999999999999999:00"), None);
+        assert_eq!(parse_minutes_seconds(&format!("{}:00", usize::MAX / 60)), None);
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use crate::{Infraction, InfractionConsequence, ScoreField, ScoringAction, WinCriterion};
+
+/// What happens to a competitor's opponent (or the competitor) when a penalty is given.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum PenaltyConsequence {
+    None,
+    OpponentAdvantage,
+    OpponentPoints(usize),
+    Disqualification
+}
+
+/// How a match that is level at the end of regulation continues.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Overtime {
+    /// No overtime, the tie-breakers alone decide the match.
+    None,
+    /// Extra scoring periods of a fixed length.
+    Periods { duration_millis: usize },
+    /// Sudden-death overtime, first score wins. Without a duration the overtime clock counts up
+    /// until someone scores.
+    GoldenScore { duration_millis: Option<usize> },
+    /// EBI-style alternating escape/submission rounds, each turn on its own clock.
+    Ebi { turn_duration_millis: usize }
+}
+
+/// The part of a match currently being contested.
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum MatchPeriod {
+    #[default]
+    Regulation,
+    Overtime(usize)
+}
+
+/// The rules a match is scored under.
+pub trait RuleSet: fmt::Debug {
+    fn name(&self) -> &str;
+
+    /// Whether the given score field is used at all. Scoring actions on unused fields are ignored.
+    fn uses_field(&self, field: ScoreField) -> bool;
+
+    /// The consequence of a competitor receiving their `penalty_count`-th penalty.
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence;
+
+    /// The criteria tried in order when a match goes the distance.
+    fn tie_breakers(&self) -> Vec<WinCriterion>;
+
+    fn overtime(&self) -> Overtime;
+
+    /// Points a scoring technique is worth. Defaults to IBJJF values.
+    fn points_for(&self, action: ScoringAction) -> usize {
+        match action {
+            ScoringAction::Takedown | ScoringAction::Sweep | ScoringAction::KneeOnBelly => 2,
+            ScoringAction::GuardPass => 3,
+            ScoringAction::Mount | ScoringAction::BackTake => 4
+        }
+    }
+
+    /// Whether points can be scored at this point of the given period.
+    fn points_allowed(&self, _period: MatchPeriod, _elapsed_millis: usize, _duration_millis: usize) -> bool {
+        true
+    }
+
+    /// Whether points can be deducted below zero as a sanction.
+    fn allows_negative_points(&self) -> bool {
+        false
+    }
+
+    /// The consequence of a competitor committing an infraction for the `count`-th time.
+    /// Defaults to IBJJF: stalling is a penalty, leaving the mat is a penalty plus two points for
+    /// the opponent, and illegal techniques and serious fouls disqualify.
+    fn infraction_consequence(&self, infraction: Infraction, _count: usize) -> InfractionConsequence {
+        match infraction {
+            Infraction::Stalling => InfractionConsequence::Penalty,
+            Infraction::LeavingTheMat => InfractionConsequence::PenaltyAndOpponentPoints(2),
+            Infraction::IllegalTechnique | Infraction::SeriousFoul => InfractionConsequence::Disqualification
+        }
+    }
+
+    /// How to rebuild the rule set when a saved match is loaded. Rule sets defined outside this
+    /// crate have none, so matches using them can't be saved.
+    fn config(&self) -> Option<RuleSetConfig> {
+        None
+    }
+}
+
+impl<R: RuleSet + ?Sized> RuleSet for Box<R> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn uses_field(&self, field: ScoreField) -> bool {
+        (**self).uses_field(field)
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        (**self).penalty_consequence(penalty_count)
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        (**self).tie_breakers()
+    }
+
+    fn overtime(&self) -> Overtime {
+        (**self).overtime()
+    }
+
+    fn points_for(&self, action: ScoringAction) -> usize {
+        (**self).points_for(action)
+    }
+
+    fn points_allowed(&self, period: MatchPeriod, elapsed_millis: usize, duration_millis: usize) -> bool {
+        (**self).points_allowed(period, elapsed_millis, duration_millis)
+    }
+
+    fn allows_negative_points(&self) -> bool {
+        (**self).allows_negative_points()
+    }
+
+    fn infraction_consequence(&self, infraction: Infraction, count: usize) -> InfractionConsequence {
+        (**self).infraction_consequence(infraction, count)
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        (**self).config()
+    }
+}
+
+/// A built-in rule set as saved with a match, including any golden score wrapper's name and
+/// duration.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum RuleSetConfig {
+    Ibjjf,
+    Adcc,
+    SubmissionOnly,
+    Ebi,
+    GoldenScore { name: String, rules: Box<RuleSetConfig>, duration_millis: Option<usize> }
+}
+
+impl RuleSetConfig {
+    pub fn build(self) -> Box<dyn RuleSet> {
+        match self {
+            RuleSetConfig::Ibjjf => Box::new(Ibjjf),
+            RuleSetConfig::Adcc => Box::new(Adcc),
+            RuleSetConfig::SubmissionOnly => Box::new(SubmissionOnly),
+            RuleSetConfig::Ebi => Box::new(Ebi),
+            RuleSetConfig::GoldenScore { name, rules, duration_millis } => Box::new(GoldenScore::new(name, rules.build(), duration_millis))
+        }
+    }
+}
+
+impl Default for Box<dyn RuleSet> {
+    fn default() -> Self {
+        Box::new(Ibjjf)
+    }
+}
+
+pub const PRESET_NAMES: [&str; 5] = ["IBJJF", "IBJJF Golden Score", "ADCC", "Submission Only", "EBI"];
+
+/// Looks up a built-in rule set by its name.
+pub fn preset(name: &str) -> Option<Box<dyn RuleSet>> {
+    match name {
+        "IBJJF" => Some(Box::new(Ibjjf)),
+        "IBJJF Golden Score" => Some(Box::new(GoldenScore::new("IBJJF Golden Score", Ibjjf, None))),
+        "ADCC" => Some(Box::new(Adcc)),
+        "Submission Only" => Some(Box::new(SubmissionOnly)),
+        "EBI" => Some(Box::new(Ebi)),
+        _ => None
+    }
+}
+
+/// Saves a rule set as its `RuleSetConfig`, failing for rule sets without one. Matches saved
+/// before configs were saved have just the preset name, which still loads.
+pub(crate) mod by_config {
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SavedRuleSet {
+        Config(RuleSetConfig),
+        Name(String)
+    }
+
+    #[allow(clippy::borrowed_box)]
+    pub fn serialize<S: Serializer>(rules: &Box<dyn RuleSet>, serializer: S) -> Result<S::Ok, S::Error> {
+        match rules.config() {
+            Some(config) => config.serialize(serializer),
+            None => Err(ser::Error::custom(format!("The {} rule set can't be saved", rules.name())))
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<dyn RuleSet>, D::Error> {
+        match SavedRuleSet::deserialize(deserializer)? {
+            SavedRuleSet::Config(config) => Ok(config.build()),
+            SavedRuleSet::Name(name) => preset(&name).ok_or_else(|| de::Error::custom(format!("Unknown rule set: {}", name)))
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ibjjf;
+
+impl RuleSet for Ibjjf {
+    fn name(&self) -> &str {
+        "IBJJF"
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        Some(RuleSetConfig::Ibjjf)
+    }
+
+    fn uses_field(&self, _field: ScoreField) -> bool {
+        true
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        match penalty_count {
+            2 => PenaltyConsequence::OpponentAdvantage,
+            3 => PenaltyConsequence::OpponentPoints(2),
+            4 => PenaltyConsequence::Disqualification,
+            _ => PenaltyConsequence::None
+        }
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        vec![WinCriterion::Points, WinCriterion::Advantages, WinCriterion::Penalties, WinCriterion::RefereeDecision]
+    }
+
+    fn overtime(&self) -> Overtime {
+        Overtime::None
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Adcc;
+
+impl RuleSet for Adcc {
+    fn name(&self) -> &str {
+        "ADCC"
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        Some(RuleSetConfig::Adcc)
+    }
+
+    fn uses_field(&self, field: ScoreField) -> bool {
+        field != ScoreField::Advantages
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        match penalty_count {
+            4 => PenaltyConsequence::Disqualification,
+            _ => PenaltyConsequence::None
+        }
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        vec![WinCriterion::Points, WinCriterion::Penalties, WinCriterion::RefereeDecision]
+    }
+
+    fn overtime(&self) -> Overtime {
+        Overtime::Periods { duration_millis: 2 * 60 * 1000 }
+    }
+
+    fn points_for(&self, action: ScoringAction) -> usize {
+        match action {
+            ScoringAction::Takedown | ScoringAction::Sweep | ScoringAction::KneeOnBelly | ScoringAction::Mount => 2,
+            ScoringAction::GuardPass | ScoringAction::BackTake => 3
+        }
+    }
+
+    /// No points are scored in the first half of regulation, overtime periods always score.
+    fn points_allowed(&self, period: MatchPeriod, elapsed_millis: usize, duration_millis: usize) -> bool {
+        match period {
+            MatchPeriod::Regulation => elapsed_millis >= duration_millis / 2,
+            MatchPeriod::Overtime(_) => true
+        }
+    }
+
+    fn allows_negative_points(&self) -> bool {
+        true
+    }
+
+    /// The first stalling call is a warning, later ones are penalties.
+    fn infraction_consequence(&self, infraction: Infraction, count: usize) -> InfractionConsequence {
+        match (infraction, count) {
+            (Infraction::Stalling, 1) => InfractionConsequence::Warning,
+            (Infraction::Stalling | Infraction::LeavingTheMat, _) => InfractionConsequence::Penalty,
+            (Infraction::IllegalTechnique | Infraction::SeriousFoul, _) => InfractionConsequence::Disqualification
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SubmissionOnly;
+
+impl RuleSet for SubmissionOnly {
+    fn name(&self) -> &str {
+        "Submission Only"
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        Some(RuleSetConfig::SubmissionOnly)
+    }
+
+    fn uses_field(&self, field: ScoreField) -> bool {
+        field == ScoreField::Penalties
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        match penalty_count {
+            3 => PenaltyConsequence::Disqualification,
+            _ => PenaltyConsequence::None
+        }
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        vec![WinCriterion::RefereeDecision]
+    }
+
+    fn overtime(&self) -> Overtime {
+        Overtime::None
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ebi;
+
+impl RuleSet for Ebi {
+    fn name(&self) -> &str {
+        "EBI"
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        Some(RuleSetConfig::Ebi)
+    }
+
+    fn uses_field(&self, field: ScoreField) -> bool {
+        field == ScoreField::Penalties
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        match penalty_count {
+            3 => PenaltyConsequence::Disqualification,
+            _ => PenaltyConsequence::None
+        }
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        Vec::new()
+    }
+
+    fn overtime(&self) -> Overtime {
+        Overtime::Ebi { turn_duration_millis: 4 * 60 * 1000 }
+    }
+}
+
+/// Wraps a rule set so that a tied match goes to sudden-death overtime.
+#[derive(Debug, Clone)]
+pub struct GoldenScore<R: RuleSet> {
+    name: String,
+    rules: R,
+    duration_millis: Option<usize>
+}
+
+impl<R: RuleSet> GoldenScore<R> {
+    pub fn new(name: impl Into<String>, rules: R, duration_millis: Option<usize>) -> GoldenScore<R> {
+        GoldenScore { name: name.into(), rules, duration_millis }
+    }
+}
+
+impl<R: RuleSet> RuleSet for GoldenScore<R> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn uses_field(&self, field: ScoreField) -> bool {
+        self.rules.uses_field(field)
+    }
+
+    fn penalty_consequence(&self, penalty_count: usize) -> PenaltyConsequence {
+        self.rules.penalty_consequence(penalty_count)
+    }
+
+    fn tie_breakers(&self) -> Vec<WinCriterion> {
+        self.rules.tie_breakers()
+    }
+
+    fn overtime(&self) -> Overtime {
+        Overtime::GoldenScore { duration_millis: self.duration_millis }
+    }
+
+    fn points_for(&self, action: ScoringAction) -> usize {
+        self.rules.points_for(action)
+    }
+
+    fn points_allowed(&self, period: MatchPeriod, elapsed_millis: usize, duration_millis: usize) -> bool {
+        self.rules.points_allowed(period, elapsed_millis, duration_millis)
+    }
+
+    fn allows_negative_points(&self) -> bool {
+        self.rules.allows_negative_points()
+    }
+
+    fn infraction_consequence(&self, infraction: Infraction, count: usize) -> InfractionConsequence {
+        self.rules.infraction_consequence(infraction, count)
+    }
+
+    fn config(&self) -> Option<RuleSetConfig> {
+        Some(RuleSetConfig::GoldenScore {
+            name: self.name.clone(),
+            rules: Box::new(self.rules.config()?),
+            duration_millis: self.duration_millis
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::{CompetitorNumber, MatchTime};
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum TimeoutKind {
+    Injury,
+    Blood,
+    Equipment
+}
+
+impl fmt::Display for TimeoutKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutKind::Injury => write!(f, "Injury"),
+            TimeoutKind::Blood => write!(f, "Blood"),
+            TimeoutKind::Equipment => write!(f, "Equipment"),
+        }
+    }
+}
+
+/// Cumulative time allowed per competitor for each kind of time-out. `None` means unlimited.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct TimeoutLimits {
+    pub injury_millis: Option<usize>,
+    pub blood_millis: Option<usize>,
+    pub equipment_millis: Option<usize>
+}
+
+impl Default for TimeoutLimits {
+    fn default() -> Self {
+        Self {
+            injury_millis: Some(2 * 60 * 1000),
+            blood_millis: None,
+            equipment_millis: None
+        }
+    }
+}
+
+impl TimeoutLimits {
+    pub fn get(&self, kind: TimeoutKind) -> Option<usize> {
+        match kind {
+            TimeoutKind::Injury => self.injury_millis,
+            TimeoutKind::Blood => self.blood_millis,
+            TimeoutKind::Equipment => self.equipment_millis
+        }
+    }
+}
+
+/// One competitor's time-out clocks. They count up and accumulate across the whole match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompetitorTimeouts {
+    pub injury: MatchTime,
+    pub blood: MatchTime,
+    pub equipment: MatchTime
+}
+
+impl CompetitorTimeouts {
+    fn new(time: &MatchTime) -> CompetitorTimeouts {
+        CompetitorTimeouts {
+            injury: time.fresh_count_up(),
+            blood: time.fresh_count_up(),
+            equipment: time.fresh_count_up()
+        }
+    }
+
+    pub fn get(&self, kind: TimeoutKind) -> &MatchTime {
+        match kind {
+            TimeoutKind::Injury => &self.injury,
+            TimeoutKind::Blood => &self.blood,
+            TimeoutKind::Equipment => &self.equipment
+        }
+    }
+
+    fn get_mut(&mut self, kind: TimeoutKind) -> &mut MatchTime {
+        match kind {
+            TimeoutKind::Injury => &mut self.injury,
+            TimeoutKind::Blood => &mut self.blood,
+            TimeoutKind::Equipment => &mut self.equipment
+        }
+    }
+}
+
+/// The time-out clocks for both competitors. Only one time-out runs at a time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchTimeouts {
+    pub competitor_one: CompetitorTimeouts,
+    pub competitor_two: CompetitorTimeouts,
+    pub limits: TimeoutLimits,
+    active: Option<(CompetitorNumber, TimeoutKind)>
+}
+
+impl Default for MatchTimeouts {
+    fn default() -> Self {
+        MatchTimeouts::new(&MatchTime::default())
+    }
+}
+
+impl MatchTimeouts {
+    /// Creates stopped time-out clocks driven by the same clock as `time`.
+    pub fn new(time: &MatchTime) -> MatchTimeouts {
+        MatchTimeouts {
+            competitor_one: CompetitorTimeouts::new(time),
+            competitor_two: CompetitorTimeouts::new(time),
+            limits: TimeoutLimits::default(),
+            active: None
+        }
+    }
+
+    pub fn competitor(&self, competitor: CompetitorNumber) -> &CompetitorTimeouts {
+        match competitor {
+            CompetitorNumber::One => &self.competitor_one,
+            CompetitorNumber::Two => &self.competitor_two
+        }
+    }
+
+    fn competitor_mut(&mut self, competitor: CompetitorNumber) -> &mut CompetitorTimeouts {
+        match competitor {
+            CompetitorNumber::One => &mut self.competitor_one,
+            CompetitorNumber::Two => &mut self.competitor_two
+        }
+    }
+
+    pub fn active(&self) -> Option<(CompetitorNumber, TimeoutKind)> {
+        self.active
+    }
+
+    pub(crate) fn start(&mut self, competitor: CompetitorNumber, kind: TimeoutKind) {
+        self.stop();
+        self.competitor_mut(competitor).get_mut(kind).start();
+        self.active = Some((competitor, kind));
+    }
+
+    pub(crate) fn stop(&mut self) {
+        if let Some((competitor, kind)) = self.active.take() {
+            self.competitor_mut(competitor).get_mut(kind).stop();
+        }
+    }
+
+    /// Stops every time-out clock at the time it showed when the match was saved and ends the
+    /// time-out in progress, for a match recovered after a crash.
+    pub(crate) fn stop_at_saved(&mut self) {
+        self.clocks_mut().for_each(MatchTime::stop_at_saved);
+        self.active = None;
+    }
+
+    /// Every competitor's time-out clocks.
+    pub(crate) fn clocks_mut(&mut self) -> impl Iterator<Item = &mut MatchTime> {
+        [&mut self.competitor_one, &mut self.competitor_two]
+            .into_iter()
+            .flat_map(|timeouts| [&mut timeouts.injury, &mut timeouts.blood, &mut timeouts.equipment])
+    }
+
+    /// Time left before the competitor reaches the limit for this kind of time-out.
+    pub fn remaining_millis(&self, competitor: CompetitorNumber, kind: TimeoutKind) -> Option<usize> {
+        let used = self.competitor(competitor).get(kind).get_elapsed_time_milliseconds();
+        self.limits.get(kind).map(|limit| limit.saturating_sub(used))
+    }
+
+    /// The competitor who has used up their allowance for any kind of time-out, if any.
+    pub fn exceeded(&self) -> Option<CompetitorNumber> {
+        let kinds = [TimeoutKind::Injury, TimeoutKind::Blood, TimeoutKind::Equipment];
+
+        [CompetitorNumber::One, CompetitorNumber::Two]
+            .into_iter()
+            .find(|competitor| kinds.iter().any(|kind| self.remaining_millis(*competitor, *kind) == Some(0)))
+    }
+}
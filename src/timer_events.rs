@@ -0,0 +1,90 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::MatchTime;
+
+/// Something that happened on the match clock.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum TimerEvent {
+    Started,
+    Paused,
+    /// The remaining time dropped to the warning threshold, e.g. 30 or 10 seconds left.
+    Warning { remaining_millis: usize },
+    Expired
+}
+
+pub type TimerObserver = Box<dyn FnMut(TimerEvent)>;
+
+/// Watches a clock between updates and notifies observers of the events in between.
+pub struct TimerWatch {
+    pub warning_thresholds_millis: Vec<usize>,
+    last_remaining: Option<usize>,
+    last_running: bool,
+    observers: Vec<TimerObserver>
+}
+
+impl fmt::Debug for TimerWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimerWatch")
+            .field("warning_thresholds_millis", &self.warning_thresholds_millis)
+            .field("last_remaining", &self.last_remaining)
+            .field("last_running", &self.last_running)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl Default for TimerWatch {
+    fn default() -> Self {
+        Self {
+            warning_thresholds_millis: vec![30_000, 10_000],
+            last_remaining: None,
+            last_running: false,
+            observers: Vec::new()
+        }
+    }
+}
+
+impl TimerWatch {
+    pub fn add_observer(&mut self, observer: TimerObserver) {
+        self.observers.push(observer);
+    }
+
+    /// Compares the clock with its state at the last update, notifying observers of every event
+    /// since then, and returns those events.
+    pub fn update(&mut self, time: &MatchTime) -> Vec<TimerEvent> {
+        let mut events = Vec::new();
+        let running = time.is_running();
+        let remaining = time.get_remaining_time_milliseconds();
+
+        if running != self.last_running {
+            events.push(if running { TimerEvent::Started } else { TimerEvent::Paused });
+        }
+
+        if let Some(last_remaining) = self.last_remaining {
+            if !time.counts_up() {
+                let mut thresholds: Vec<usize> = self.warning_thresholds_millis
+                    .iter()
+                    .copied()
+                    .filter(|threshold| last_remaining > *threshold && remaining <= *threshold && remaining > 0)
+                    .collect();
+                thresholds.sort_unstable_by(|a, b| b.cmp(a));
+                events.extend(thresholds.into_iter().map(|remaining_millis| TimerEvent::Warning { remaining_millis }));
+
+                if last_remaining > 0 && remaining == 0 {
+                    events.push(TimerEvent::Expired);
+                }
+            }
+        }
+
+        self.last_running = running;
+        self.last_remaining = Some(remaining);
+
+        for event in &events {
+            for observer in &mut self.observers {
+                observer(*event);
+            }
+        }
+
+        events
+    }
+}